@@ -1,54 +1,80 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+use advent_of_code_2025::{days::DAYS, get_input, SolveContext};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
 use std::hint::black_box;
 
+/// Benchmarks every registered day as `dayNN/real` and `dayNN/test`, so a single day
+/// can be selected with a criterion filter such as `cargo bench -- day03`.
 fn criterion_benchmark(c: &mut Criterion) {
-    macro_rules! bench_day {
-        ($day:tt) => {{
-            const INPUT: &str = include_str!(concat!(
-                std::env!("AOC_CACHE"),
-                "/2025_",
-                stringify!($day),
-                ".txt"
-            ));
-            c.bench_function(stringify!($day), |b| {
-                b.iter(|| advent_of_code_2025::days::$day::solve(black_box(INPUT)))
+    for day in DAYS {
+        let mut group = c.benchmark_group(day.name);
+        for (label, is_test) in [("real", false), ("test", true)] {
+            let input = match get_input(day.name, is_test) {
+                Ok(input) if !input.trim().is_empty() => input,
+                Ok(_) => {
+                    eprintln!("skipping {}/{label}: input is empty", day.name);
+                    continue;
+                }
+                Err(err) => {
+                    eprintln!("skipping {}/{label}: {err:#}", day.name);
+                    continue;
+                }
+            };
+            // a fresh context each time, as phase timings accumulate in it
+            group.bench_function(label, |b| {
+                b.iter_batched_ref(
+                    || SolveContext::new(&input).with_test(is_test),
+                    |ctx| (day.solve)(black_box(ctx)),
+                    BatchSize::SmallInput,
+                )
             });
-            const INPUT_TEST: &str =
-                include_str!(concat!("../test_input/", stringify!($day), ".txt"));
-            c.bench_function(concat!(stringify!($day), " test"), |b| {
-                b.iter(|| advent_of_code_2025::days::$day::solve(black_box(INPUT_TEST)))
-            });
-        }};
-        ($day:tt, is_test) => {{
-            const INPUT: &str = include_str!(concat!(
-                std::env!("AOC_CACHE"),
-                "/2025_",
-                stringify!($day),
-                ".txt"
-            ));
-            c.bench_function(stringify!($day), |b| {
-                b.iter(|| advent_of_code_2025::days::$day::solve(black_box(INPUT), false))
-            });
-            const INPUT_TEST: &str =
-                include_str!(concat!("../test_input/", stringify!($day), ".txt"));
-            c.bench_function(concat!(stringify!($day), " test"), |b| {
-                b.iter(|| advent_of_code_2025::days::$day::solve(black_box(INPUT_TEST), true))
-            });
-        }};
+        }
+        group.finish();
+    }
+}
+
+/// A day 3 input shaped like the real one: 200 banks of 100 digits from 1 to 9.
+fn generated_banks() -> String {
+    let mut state = 0x2545_f491_u32;
+    let mut input = String::new();
+    for _ in 0..200 {
+        for _ in 0..100 {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            input.push(char::from(b'1' + (state % 9) as u8));
+        }
+        input.push('\n');
     }
+    input
+}
+
+/// Benchmarks each day 3 kernel as `day03/kernels/<kernel>`, on the real input if it is
+/// cached and a generated one otherwise.
+fn day03_kernels(c: &mut Criterion) {
+    let Some(day) = DAYS.iter().find(|day| day.name == "day03") else {
+        return;
+    };
+    let input = match get_input(day.name, false) {
+        Ok(input) if !input.trim().is_empty() => input,
+        _ => generated_banks(),
+    };
 
-    // bench_day!(day01);
-    // bench_day!(day02);
-    // bench_day!(day03, is_test);
-    bench_day!(day04);
-    // bench_day!(day05);
-    // bench_day!(day06);
-    // bench_day!(day07);
-    // bench_day!(day08);
-    // bench_day!(day09);
-    // bench_day!(day10);
-    // bench_day!(day11);
+    let mut kernels = vec!["auto", "simple", "tail_copy", "window"];
+    if cfg!(all(feature = "simd", target_arch = "x86_64")) {
+        kernels.push("simd");
+    }
+    let mut group = c.benchmark_group("day03/kernels");
+    for kernel in kernels {
+        group.bench_function(kernel, |b| {
+            b.iter_batched_ref(
+                || SolveContext::new(&input).with_param("best_twelve", kernel),
+                |ctx| (day.solve)(black_box(ctx)),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
 }
 
-criterion_group!(benches, criterion_benchmark);
+criterion_group!(benches, criterion_benchmark, day03_kernels);
 criterion_main!(benches);
@@ -0,0 +1,138 @@
+use advent_of_code_2025::parse::{self, combinators, RowLayout};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+/// Day 1 shaped input: one `L`/`R` turn per line.
+fn turns() -> String {
+    (0..4096_u32)
+        .map(|i| {
+            let dir = if i % 3 == 0 { 'L' } else { 'R' };
+            format!("{dir}{}\n", (i * 7919) % 1000)
+        })
+        .collect()
+}
+
+/// Day 2 shaped input: a single line of comma separated ranges.
+fn ranges() -> String {
+    (0..4096_u64)
+        .map(|i| format!("{}-{}", i * 1_000_003, i * 1_000_003 + 500))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Day 3/4 shaped input: fixed width rows of digits.
+fn rows() -> String {
+    (0..1024_u32)
+        .map(|i| {
+            let row = (0..100).map(|j| char::from(b'1' + ((i + j) % 9) as u8));
+            row.chain(['\n']).collect::<String>()
+        })
+        .collect()
+}
+
+/// Each helper against the loop a day would write by hand for the same job.
+fn criterion_benchmark(c: &mut Criterion) {
+    let input = turns();
+    let mut group = c.benchmark_group("parse/turns");
+    group.bench_function("hand", |b| {
+        b.iter(|| {
+            let mut total = 0_i64;
+            let mut number = 0_i64;
+            let mut dir = 1;
+            for &byte in black_box(input.as_bytes()) {
+                match byte {
+                    b'L' => dir = -1,
+                    b'R' => dir = 1,
+                    b'0'..=b'9' => number = number * 10 + (byte - b'0') as i64,
+                    b'\n' => {
+                        total += dir * number;
+                        number = 0;
+                    }
+                    _ => {}
+                }
+            }
+            total
+        })
+    });
+    group.bench_function("lines", |b| {
+        b.iter(|| {
+            parse::lines(black_box(input.as_bytes()))
+                .map(|line| {
+                    let (number, _) = parse::unsigned::<i64>(&line[1..]).unwrap();
+                    if line[0] == b'L' {
+                        -number
+                    } else {
+                        number
+                    }
+                })
+                .sum::<i64>()
+        })
+    });
+    group.bench_function("unsigned", |b| {
+        b.iter(|| {
+            let mut total = 0_i64;
+            let mut rest = black_box(input.as_bytes());
+            while let [dir, tail @ ..] = rest {
+                let (number, len) = parse::unsigned::<i64>(tail).unwrap();
+                total += if *dir == b'L' { -number } else { number };
+                rest = tail.get(len + 1..).unwrap_or_default();
+            }
+            total
+        })
+    });
+    group.finish();
+
+    let input = ranges();
+    let mut group = c.benchmark_group("parse/ranges");
+    group.bench_function("hand", |b| {
+        b.iter(|| {
+            black_box(&input)
+                .split(',')
+                .map(|ids| {
+                    let (a, b) = ids.split_once('-').unwrap();
+                    a.parse::<u64>().unwrap() ^ b.parse::<u64>().unwrap()
+                })
+                .fold(0, |acc, x| acc ^ x)
+        })
+    });
+    group.bench_function("integers", |b| {
+        b.iter(|| parse::integers::<u64>(black_box(input.as_bytes())).fold(0, |acc, x| acc ^ x))
+    });
+    group.bench_function("combinators", |b| {
+        b.iter(|| {
+            combinators::finish(
+                combinators::comma_list(combinators::range::<u64>),
+                black_box(&input),
+            )
+            .unwrap()
+            .into_iter()
+            .fold(0, |acc, (a, b)| acc ^ a ^ b)
+        })
+    });
+    group.finish();
+
+    let input = rows();
+    let mut group = c.benchmark_group("parse/rows");
+    group.bench_function("hand", |b| {
+        b.iter(|| {
+            black_box(&input)
+                .lines()
+                .map(|row| row.as_bytes()[row.len() / 2] as u64)
+                .sum::<u64>()
+        })
+    });
+    group.bench_function("row_layout", |b| {
+        b.iter(|| {
+            let bytes = black_box(input.as_bytes());
+            let layout = RowLayout::detect(bytes).unwrap();
+            layout
+                .rows(bytes)
+                .map(|row| row[layout.width() / 2] as u64)
+                .sum::<u64>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,126 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+/// A multi-line answer drawn with characters, such as letters spelt out in `#` and `.`.
+///
+/// The art is normalised when it is made: trailing whitespace is stripped from every
+/// line and blank lines are dropped from the top and bottom, so answers compare equal
+/// however the solver happened to pad them. Letters in the advent of code fonts are
+/// read with [`ocr`](crate::ocr) at the same time.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "StoredArt")]
+pub struct AsciiArt {
+    art: String,
+    text: Option<String>,
+}
+
+/// What is read back of a serialized [`AsciiArt`]. The stored text may be stale or
+/// edited, so it is ignored and the art is read again.
+#[derive(Deserialize)]
+struct StoredArt {
+    art: String,
+}
+
+impl From<StoredArt> for AsciiArt {
+    fn from(stored: StoredArt) -> Self {
+        AsciiArt::new(stored.art)
+    }
+}
+
+impl AsciiArt {
+    pub fn new(art: impl AsRef<str>) -> Self {
+        let lines = art
+            .as_ref()
+            .lines()
+            .map(str::trim_end)
+            .skip_while(|line| line.is_empty())
+            .collect::<Vec<_>>();
+        let len = lines
+            .iter()
+            .rposition(|line| !line.is_empty())
+            .map_or(0, |i| i + 1);
+
+        let art = lines[..len].join("\n");
+        let text = crate::ocr::read_art(&art);
+        Self { art, text }
+    }
+
+    /// Draws rows of `width` cells as `#` where set and `.` where not. Rows of no cells
+    /// draw nothing.
+    pub fn from_cells(width: usize, cells: impl IntoIterator<Item = bool>) -> Self {
+        let mut art = String::new();
+        if width == 0 {
+            return Self::new(art);
+        }
+        for (i, cell) in cells.into_iter().enumerate() {
+            if i > 0 && i % width == 0 {
+                art.push('\n');
+            }
+            art.push(if cell { '#' } else { '.' });
+        }
+        Self::new(art)
+    }
+
+    pub fn art(&self) -> &str {
+        &self.art
+    }
+
+    /// The letters the art spells, if they could be read.
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+}
+
+impl From<&str> for AsciiArt {
+    fn from(art: &str) -> Self {
+        AsciiArt::new(art)
+    }
+}
+
+impl Display for AsciiArt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.art)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ascii_art::AsciiArt;
+
+    #[test]
+    fn normalises_padding() {
+        let art = AsciiArt::new("\n  \n#..#  \n####\n#..#\n\n");
+        assert_eq!("#..#\n####\n#..#", art.art());
+        assert_eq!(art, AsciiArt::new("#..#\n####\n#..#"));
+        assert_eq!("", AsciiArt::new("\n \n").art());
+    }
+
+    #[test]
+    fn reads_letters() {
+        let art = AsciiArt::new("#..#.###\n#..#..#.\n####..#.\n#..#..#.\n#..#..#.\n#..#.###\n");
+        assert_eq!(Some("HI"), art.text());
+        assert_eq!(None, AsciiArt::new("#.\n.#").text());
+    }
+
+    #[test]
+    fn draws_cells() {
+        let cells = [true, false, true, false, true, false];
+        assert_eq!("#.#\n.#.", AsciiArt::from_cells(3, cells).art());
+        assert_eq!("", AsciiArt::from_cells(0, cells).art());
+    }
+
+    #[test]
+    fn reads_letters_again_when_deserialized() {
+        let art = AsciiArt::new("#..#.###\n#..#..#.\n####..#.\n#..#..#.\n#..#..#.\n#..#.###\n");
+        let json = serde_json::to_string(&art).unwrap();
+        assert_eq!(art, serde_json::from_str(&json).unwrap());
+
+        let stale = json.replace("HI", "NO");
+        assert_ne!(json, stale);
+        let parsed: AsciiArt = serde_json::from_str(&stale).unwrap();
+        assert_eq!(Some("HI"), parsed.text());
+        let parsed: AsciiArt = serde_json::from_str(r##"{"art":"#.\n.#  "}"##).unwrap();
+        assert_eq!(AsciiArt::new("#.\n.#"), parsed);
+    }
+}
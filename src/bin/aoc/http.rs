@@ -0,0 +1,129 @@
+use std::{io::ErrorKind, path::PathBuf, time::Duration};
+
+use anyhow::{bail, Context};
+use clap::Args;
+use reqwest::blocking::{Client, ClientBuilder};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// How to talk to adventofcode.com. Each setting is taken from its flag, then its env
+/// var, then the config file (`AOC_CONFIG`, or `config.txt` in `AOC_CACHE`) and
+/// finally a default.
+#[derive(Args, Debug, Clone, Default)]
+pub struct HttpArgs {
+    /// The server to talk to [default: https://adventofcode.com]
+    #[arg(long, global = true, env = "AOC_BASE_URL")]
+    base_url: Option<String>,
+    /// Contact details sent in the user agent [default: this repository's url]
+    #[arg(long, global = true, env = "AOC_CONTACT")]
+    contact: Option<String>,
+    /// Http request timeout in seconds [default: 30]
+    #[arg(long, global = true, env = "AOC_TIMEOUT")]
+    timeout: Option<u64>,
+    /// Never touch the network, failing if something is not already cached
+    #[arg(long, global = true, env = "AOC_OFFLINE")]
+    offline: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpConfig {
+    pub base_url: String,
+    pub user_agent: String,
+    pub timeout: Duration,
+    pub offline: bool,
+}
+
+impl HttpArgs {
+    pub fn resolve(self, year: i32) -> anyhow::Result<HttpConfig> {
+        let file = read_config_file()?;
+        let from_file = |key: &str| {
+            file.iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, value)| value.clone())
+        };
+
+        let base_url = self
+            .base_url
+            .or_else(|| from_file("base_url"))
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        let contact = self
+            .contact
+            .or_else(|| from_file("contact"))
+            .unwrap_or_else(|| format!("https://github.com/jchevertonwynne/advent-of-code-{year}"));
+        let timeout = match self.timeout {
+            Some(timeout) => timeout,
+            None => from_file("timeout")
+                .map(|timeout| timeout.parse())
+                .transpose()
+                .context("failed to parse timeout in config file")?
+                .unwrap_or(DEFAULT_TIMEOUT_SECS),
+        };
+        let offline = self.offline
+            || from_file("offline")
+                .map(|offline| offline.parse())
+                .transpose()
+                .context("failed to parse offline in config file")?
+                .unwrap_or(false);
+
+        Ok(HttpConfig {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            user_agent: contact,
+            timeout: Duration::from_secs(timeout),
+            offline,
+        })
+    }
+}
+
+impl HttpConfig {
+    #[cfg(test)]
+    pub fn for_test(base_url: String) -> Self {
+        Self {
+            base_url,
+            user_agent: "aoc tests".to_string(),
+            timeout: Duration::from_secs(5),
+            offline: false,
+        }
+    }
+
+    pub fn client(&self) -> anyhow::Result<Client> {
+        if self.offline {
+            bail!("refusing to make http requests in offline mode");
+        }
+
+        ClientBuilder::new()
+            .user_agent(&self.user_agent)
+            .timeout(self.timeout)
+            .build()
+            .context("failed to build http client")
+    }
+}
+
+/// Reads `key = value` lines, ignoring blank lines and `#` comments.
+fn read_config_file() -> anyhow::Result<Vec<(String, String)>> {
+    let path = match std::env::var_os("AOC_CONFIG") {
+        Some(path) => PathBuf::from(path),
+        None => match std::env::var_os("AOC_CACHE") {
+            Some(cache) => PathBuf::from(cache).join("config.txt"),
+            None => return Ok(vec![]),
+        },
+    };
+
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err).with_context(|| format!("failed to read {path:?}")),
+    };
+
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (key, value) = line
+                .split_once('=')
+                .with_context(|| format!("expected key = value in {path:?}, got {line:?}"))?;
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
@@ -0,0 +1,90 @@
+use advent_of_code_2025::ledger::{parse_answer, Ledger, Verdict};
+use anyhow::{bail, Context};
+use chrono::{Datelike, Utc};
+use clap::{Args, Subcommand};
+
+use crate::PackageName;
+
+#[derive(Args, Debug)]
+pub struct LedgerArgs {
+    #[command(subcommand)]
+    command: LedgerCommand,
+    /// The year of the ledger
+    #[arg(short, long, global = true, default_value_t = Utc::now().year())]
+    year: i32,
+}
+
+#[derive(Subcommand, Debug)]
+enum LedgerCommand {
+    /// List every recorded answer, optionally only for one day
+    List { day: Option<u32> },
+    /// Record a verdict for an answer that was submitted elsewhere
+    Record {
+        day: u32,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// One of correct, too_high, too_low or wrong
+        verdict: Verdict,
+        answer: String,
+    },
+    /// Check whether an answer is worth submitting
+    Check {
+        day: u32,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        answer: String,
+    },
+    /// Remove a recorded answer
+    Forget {
+        day: u32,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        answer: String,
+    },
+}
+
+pub fn ledger(args: LedgerArgs) -> anyhow::Result<()> {
+    let mut ledger = Ledger::for_year(args.year)?;
+
+    match args.command {
+        LedgerCommand::List { day } => {
+            for ((entry_day, part), entry) in ledger.entries() {
+                if day.is_some_and(|day| day != entry_day) {
+                    continue;
+                }
+                println!(
+                    "{pkg_name}\t{part}\t{verdict}\t{answer}",
+                    pkg_name = PackageName(entry_day),
+                    verdict = entry.verdict,
+                    answer = entry.answer
+                );
+            }
+        }
+        LedgerCommand::Record {
+            day,
+            part,
+            verdict,
+            answer,
+        } => {
+            ledger.record(day, part, parse_answer(&answer), verdict)?;
+            ledger.save()?;
+        }
+        LedgerCommand::Check { day, part, answer } => {
+            ledger
+                .check(day, part, &parse_answer(&answer))
+                .with_context(|| format!("{answer} should not be submitted"))?;
+            println!("{answer} has not been ruled out");
+        }
+        LedgerCommand::Forget { day, part, answer } => {
+            if !ledger.forget(day, part, &parse_answer(&answer)) {
+                bail!(
+                    "{answer} is not recorded for {} part {part}",
+                    PackageName(day)
+                );
+            }
+            ledger.save()?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,339 @@
+use anyhow::{anyhow, bail, Context};
+use chrono::{Datelike, Utc};
+use clap::{Parser, Subcommand};
+use tracing::info;
+
+use crate::http::{HttpArgs, HttpConfig};
+
+use std::{
+    fmt::{Display, Formatter},
+    fs::File,
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+mod http;
+mod ledger;
+mod registry;
+mod submit;
+#[cfg(test)]
+mod test_server;
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    setup_tracing()?;
+    if let Some(command) = args.command {
+        return match command {
+            Command::Submit(submit_args) => submit::submit(submit_args, args.http),
+            Command::Ledger(ledger_args) => ledger::ledger(ledger_args),
+        };
+    }
+
+    let root = match args.root {
+        Some(root) => root,
+        None => std::env::current_dir().context("failed to find current dir")?,
+    };
+    ensure_in_aoc_repository(&root)?;
+    let pkg_name = PackageName(args.day);
+    if !args.download_only {
+        write_solver_file(&root, pkg_name, args.overwrite)
+            .context("could not write solver file")?;
+        ensure_test_file(&root, pkg_name, args.overwrite).context("failed to make test file")?;
+        registry::update_mod_file(&root, pkg_name).context("could not update mod file")?;
+    }
+    let http = args.http.resolve(args.year)?;
+    ensure_cached_input(pkg_name, args.year, &http).context("could not ensure cached input")?;
+    Ok(())
+}
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None, args_conflicts_with_subcommands = true)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// The day to download
+    #[arg(default_value_t = Utc::now().day())]
+    day: u32,
+    /// The year to download
+    #[arg(short, long, default_value_t = Utc::now().year())]
+    year: i32,
+    /// Whether existing solver & test input files should be overwritten
+    #[arg(short, long, default_value_t = false)]
+    overwrite: bool,
+    /// The root of the advent of code repository, defaults to the current dir
+    #[arg(short, long)]
+    root: Option<PathBuf>,
+    /// Only download input (if not already cached)
+    #[arg(short, default_value_t = false)]
+    download_only: bool,
+    #[command(flatten)]
+    http: HttpArgs,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run a day's solver on the real input and submit one part's answer
+    Submit(submit::SubmitArgs),
+    /// Inspect and manage the local record of submitted answers
+    Ledger(ledger::LedgerArgs),
+}
+
+fn setup_tracing() -> Result<(), anyhow::Error> {
+    tracing_subscriber::fmt()
+        .try_init()
+        .map_err(|err| anyhow!("failed to setup tracing: {}", err))?;
+
+    Ok(())
+}
+
+fn ensure_in_aoc_repository(root: &Path) -> Result<(), anyhow::Error> {
+    let expected_package = "name = \"advent-of-code-";
+    let manifest = std::fs::read_to_string(root.join("Cargo.toml"))
+        .with_context(|| format!("no Cargo.toml in {root:?}"))?;
+    if !manifest
+        .lines()
+        .any(|line| line.starts_with(expected_package))
+    {
+        bail!("{root:?} is not an advent-of-code- package");
+    }
+    if !root.join("src/days/mod.rs").is_file() {
+        bail!("{root:?} has no src/days/mod.rs");
+    }
+
+    Ok(())
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+struct PackageName(u32);
+
+impl Display for PackageName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day{:0>2}", self.0)
+    }
+}
+
+fn write_solver_file(
+    root: &Path,
+    pkg_name: PackageName,
+    overwrite: bool,
+) -> Result<(), anyhow::Error> {
+    // a day that has grown submodules lives in `src/days/dayNN/mod.rs`; a stub next to it
+    // would not compile, and a stub over it would delete the solver
+    let dir = root.join(format!("src/days/{pkg_name}"));
+    if dir.is_dir() {
+        bail!("{pkg_name} is a directory at {dir:?}, refusing to write a stub for it");
+    }
+
+    let solver = format!(
+        r#"use anyhow::Result;
+use crate::{{DayResult, IntoDayResult, SolveContext}};
+
+pub fn solve(_ctx: &SolveContext<'_>) -> Result<DayResult> {{
+    ().into_result()
+}}
+
+#[cfg(test)]
+mod tests {{
+    use crate::{{days::{pkg_name}::solve, IntoDayResult, SolveContext}};
+
+    #[ignore]
+    #[test]
+    fn works_for_example() {{
+        const INPUT: &str = include_str!("../../test_input/{pkg_name}.txt");
+        let solution = solve(&SolveContext::new(INPUT).with_test(true)).unwrap();
+        assert_eq!(
+            ().into_day_result(),
+            solution
+        );
+    }}
+}}"#
+    );
+    let mut options = File::options();
+    options.write(true);
+    if overwrite {
+        options.create(true).truncate(true);
+    } else {
+        options.create_new(true);
+    }
+
+    options
+        .open(root.join(format!("src/days/{pkg_name}.rs")))
+        .with_context(|| format!("failed to open solver file for {pkg_name}"))?
+        .write_all(solver.as_bytes())
+        .context("failed to write solver file")?;
+
+    Ok(())
+}
+
+fn ensure_cached_input(pkg_name: PackageName, year: i32, http: &HttpConfig) -> anyhow::Result<()> {
+    let cache_folder = std::env::var("AOC_CACHE").context("failed to find AOC_CACHE env var")?;
+    let cache_file = format!("{cache_folder}/{year}_{pkg_name}.txt");
+
+    let Err(err) = std::fs::read_to_string(&cache_file) else {
+        info!("serving cached input");
+        return Ok(());
+    };
+    if err.kind() != ErrorKind::NotFound {
+        return Err(err).context("failed to read cache input file");
+    }
+    if http.offline {
+        bail!("{cache_file} is not cached and offline mode is enabled");
+    }
+
+    let session = std::env::var("AOC_SESSION").context("expected AOC_SESSION env var")?;
+    retrieve_and_cache_fresh_input(pkg_name, year, &session, &cache_folder, &cache_file, http)?;
+
+    Ok(())
+}
+
+fn retrieve_and_cache_fresh_input(
+    pkg_name: PackageName,
+    year: i32,
+    session: &str,
+    cache_folder: &str,
+    cache_file: &str,
+    http: &HttpConfig,
+) -> Result<String, anyhow::Error> {
+    let response = retrieve_fresh(pkg_name, year, session, http)?;
+    cache_response(cache_folder, cache_file, &response)?;
+
+    Ok(response)
+}
+
+fn retrieve_fresh(
+    pkg_name: PackageName,
+    year: i32,
+    session: &str,
+    http: &HttpConfig,
+) -> Result<String, anyhow::Error> {
+    let url = format!(
+        "{base_url}/{year}/day/{day}/input",
+        base_url = http.base_url,
+        day = pkg_name.0
+    );
+    info!("retrieving input from url {url}");
+
+    let client = http.client()?;
+
+    let request = client
+        .get(url)
+        .header("Cookie", format!("session={session}"))
+        .build()
+        .context("failed to build http request")?;
+
+    let response = client
+        .execute(request)
+        .context("failed to perform http request")?;
+
+    let status = response.status();
+    let body = response
+        .text()
+        .context("failed to read http response body")?;
+    if !status.is_success() {
+        tracing::error!(
+            status = status.as_u16(),
+            body,
+            "bad http response code returned"
+        );
+        bail!("bad http response code returned: {status}");
+    }
+
+    info!("retrieved input");
+
+    Ok(body)
+}
+
+fn cache_response(
+    cache_folder: &str,
+    cache_file: &str,
+    response: &str,
+) -> Result<(), anyhow::Error> {
+    if !Path::new(cache_folder).is_dir() {
+        std::fs::create_dir_all(cache_folder).context("failed to create aoc cache directory")?;
+        info!("created {cache_folder}")
+    }
+
+    std::fs::write(cache_file, response.as_bytes())
+        .context("failed to write aoc input to cache")?;
+    info!("cached input to {cache_file}");
+
+    Ok(())
+}
+
+fn ensure_test_file(root: &Path, pkg_name: PackageName, overwrite: bool) -> anyhow::Result<()> {
+    let mut options = File::options();
+    options.write(true);
+    if overwrite {
+        options.create(true).truncate(true);
+    } else {
+        options.create_new(true);
+    }
+
+    options
+        .open(root.join(format!("test_input/{pkg_name}.txt")))
+        .context("failed to make test file")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cache_response, http::HttpConfig, retrieve_fresh, test_server, PackageName};
+
+    #[test]
+    fn retrieves_input_from_configured_server() {
+        let (base_url, server) = test_server::serve_once(200, "1\n2\n3\n".to_string());
+
+        let input = retrieve_fresh(
+            PackageName(7),
+            2025,
+            "secret",
+            &HttpConfig::for_test(base_url),
+        )
+        .unwrap();
+        assert_eq!("1\n2\n3\n", input);
+
+        let request = server.join().unwrap();
+        assert_eq!("GET /2025/day/7/input HTTP/1.1", request.request_line);
+        assert_eq!("session=secret", request.cookie);
+        assert_eq!("aoc tests", request.user_agent);
+    }
+
+    #[test]
+    fn fails_on_bad_status() {
+        let (base_url, server) = test_server::serve_once(404, "not found".to_string());
+
+        let err = retrieve_fresh(
+            PackageName(7),
+            2025,
+            "secret",
+            &HttpConfig::for_test(base_url),
+        )
+        .unwrap_err();
+        assert_eq!(
+            "bad http response code returned: 404 Not Found",
+            err.to_string()
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn caches_into_a_missing_nested_folder() {
+        let root = std::env::temp_dir().join(format!("aoc-cache-{}", std::process::id()));
+        let folder = root.join("nested/cache");
+        let file = folder.join("day07.txt");
+
+        cache_response(folder.to_str().unwrap(), file.to_str().unwrap(), "1\n").unwrap();
+        assert_eq!("1\n", std::fs::read_to_string(&file).unwrap());
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn offline_mode_never_builds_a_client() {
+        let mut config = HttpConfig::for_test("http://127.0.0.1:9".to_string());
+        config.offline = true;
+
+        let err = retrieve_fresh(PackageName(7), 2025, "secret", &config).unwrap_err();
+        assert!(err.to_string().contains("offline"), "{err}");
+    }
+}
@@ -0,0 +1,217 @@
+use std::path::Path;
+
+use crate::PackageName;
+use anyhow::Context;
+use nom::{
+    bytes::complete::tag,
+    character::complete::space0,
+    combinator::{all_consuming, map, opt, rest},
+    sequence::preceded,
+    IResult, Parser,
+};
+
+/// Adds a day to the `register_days!` block in `src/days/mod.rs`.
+pub fn update_mod_file(root: &Path, pkg_name: PackageName) -> anyhow::Result<()> {
+    let path = root.join("src/days/mod.rs");
+    let contents = std::fs::read_to_string(&path).context("failed to read mod file")?;
+    let updated = register_day(&contents, pkg_name)?;
+    if updated != contents {
+        write_atomically(&path, &updated).context("failed to update mod file")?;
+    }
+
+    Ok(())
+}
+
+/// Writes to a sibling temp file and renames it over `path`, so an interrupted write
+/// never leaves a truncated file behind.
+fn write_atomically(path: &Path, contents: &str) -> anyhow::Result<()> {
+    let file_name = path
+        .file_name()
+        .with_context(|| format!("{path:?} has no file name"))?
+        .to_string_lossy();
+    let tmp = path.with_file_name(format!(".{file_name}.tmp"));
+    std::fs::write(&tmp, contents).with_context(|| format!("failed to write {tmp:?}"))?;
+    std::fs::rename(&tmp, path).with_context(|| format!("failed to rename {tmp:?} to {path:?}"))
+}
+
+struct RegistryLine<'a> {
+    indent: &'a str,
+    pkg_name: PackageName,
+    comma: bool,
+    rest: &'a str,
+}
+
+/// Parses a `    dayNN, // optional comment` line from inside `register_days!`.
+fn parse_registry_line(input: &str) -> IResult<&str, RegistryLine<'_>> {
+    all_consuming(map(
+        (
+            space0,
+            preceded(tag("day"), nom::character::complete::u32),
+            opt(tag(",")),
+            rest,
+        ),
+        |(indent, day, comma, rest)| RegistryLine {
+            indent,
+            pkg_name: PackageName(day),
+            comma: comma.is_some(),
+            rest,
+        },
+    ))
+    .parse(input)
+    .and_then(|(remaining, line)| {
+        let trimmed = line.rest.trim();
+        if trimmed.is_empty() || trimmed.starts_with("//") {
+            Ok((remaining, line))
+        } else {
+            Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Verify,
+            )))
+        }
+    })
+}
+
+/// Attributes and comments directly above an entry belong to it.
+fn is_attached(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("#[") || line.starts_with("//")
+}
+
+fn register_day(contents: &str, pkg_name: PackageName) -> anyhow::Result<String> {
+    let mut lines = contents.lines().map(str::to_string).collect::<Vec<_>>();
+    let start = lines
+        .iter()
+        .position(|line| line.contains("register_days!") && line.trim_end().ends_with('{'))
+        .context("no `register_days! {` block found")?;
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| line.trim_start().starts_with('}'))
+        .map(|i| start + 1 + i)
+        .context("`register_days!` block is never closed")?;
+
+    let entries = (start + 1..end)
+        .filter_map(|i| {
+            parse_registry_line(&lines[i])
+                .ok()
+                .map(|(_, line)| (i, line.pkg_name))
+        })
+        .collect::<Vec<_>>();
+    if entries.iter().any(|&(_, day)| day == pkg_name) {
+        return Ok(contents.to_string());
+    }
+
+    let indent = entries
+        .first()
+        .and_then(|&(i, _)| parse_registry_line(&lines[i]).ok())
+        .map_or("    ".to_string(), |(_, line)| line.indent.to_string());
+
+    let insert_at = match entries.iter().find(|&&(_, day)| day > pkg_name) {
+        Some(&(mut i, _)) => {
+            while i > start + 1 && is_attached(&lines[i - 1]) {
+                i -= 1;
+            }
+            i
+        }
+        None => match entries.last() {
+            Some(&(i, _)) => {
+                let (_, last) = parse_registry_line(&lines[i])
+                    .map_err(|err| anyhow::anyhow!("failed to reparse registry line: {err}"))?;
+                if !last.comma {
+                    lines[i] = format!("{}{},{}", last.indent, last.pkg_name, last.rest);
+                }
+                i + 1
+            }
+            None => end,
+        },
+    };
+    lines.insert(insert_at, format!("{indent}{pkg_name},"));
+
+    Ok(join_lines(contents, lines))
+}
+
+fn join_lines(original: &str, lines: Vec<String>) -> String {
+    let mut joined = lines.join("\n");
+    if original.ends_with('\n') {
+        joined.push('\n');
+    }
+    joined
+}
+
+#[cfg(test)]
+mod tests {
+    use super::register_day;
+    use crate::PackageName;
+
+    #[test]
+    fn registers_day_in_order() {
+        let mod_file = "crate::register_days! {\n    day01,\n    day03,\n}\n";
+        assert_eq!(
+            "crate::register_days! {\n    day01,\n    day02,\n    day03,\n}\n",
+            register_day(mod_file, PackageName(2)).unwrap()
+        );
+        assert_eq!(
+            "crate::register_days! {\n    day01,\n    day03,\n    day04,\n}\n",
+            register_day(mod_file, PackageName(4)).unwrap()
+        );
+        assert_eq!(mod_file, register_day(mod_file, PackageName(3)).unwrap());
+    }
+
+    #[test]
+    fn preserves_comments_attributes_and_other_content() {
+        let mod_file = "//! All the days\n\
+            \n\
+            crate::register_days! {\n\
+            \x20   // the first week\n\
+            \x20   day01, // fast\n\
+            \n\
+            \x20   #[cfg(feature = \"slow\")]\n\
+            \x20   day05\n\
+            }\n\
+            \n\
+            pub mod helpers;\n";
+        assert_eq!(
+            "//! All the days\n\
+            \n\
+            crate::register_days! {\n\
+            \x20   // the first week\n\
+            \x20   day01, // fast\n\
+            \n\
+            \x20   day03,\n\
+            \x20   #[cfg(feature = \"slow\")]\n\
+            \x20   day05\n\
+            }\n\
+            \n\
+            pub mod helpers;\n",
+            register_day(mod_file, PackageName(3)).unwrap()
+        );
+        assert_eq!(
+            "//! All the days\n\
+            \n\
+            crate::register_days! {\n\
+            \x20   // the first week\n\
+            \x20   day01, // fast\n\
+            \n\
+            \x20   #[cfg(feature = \"slow\")]\n\
+            \x20   day05,\n\
+            \x20   day06,\n\
+            }\n\
+            \n\
+            pub mod helpers;\n",
+            register_day(mod_file, PackageName(6)).unwrap()
+        );
+    }
+
+    #[test]
+    fn registers_into_empty_block() {
+        assert_eq!(
+            "crate::register_days! {\n    day01,\n}",
+            register_day("crate::register_days! {\n}", PackageName(1)).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_mod_file_without_registry() {
+        assert!(register_day("pub mod day01;\n", PackageName(2)).is_err());
+        assert!(register_day("crate::register_days! {\n    day01,\n", PackageName(2)).is_err());
+    }
+}
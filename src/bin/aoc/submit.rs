@@ -0,0 +1,292 @@
+use std::{
+    fmt::{Display, Formatter},
+    sync::LazyLock,
+    time::Duration,
+};
+
+use advent_of_code_2025::{
+    days::DAYS,
+    ledger::{Ledger, Verdict},
+    Answers, SolveContext,
+};
+use anyhow::{bail, Context};
+use chrono::{Datelike, Utc};
+use clap::Args;
+use regex::Regex;
+use tracing::info;
+
+use crate::{
+    http::{HttpArgs, HttpConfig},
+    PackageName,
+};
+
+#[derive(Args, Debug)]
+pub struct SubmitArgs {
+    /// The day to submit
+    day: u32,
+    /// The part to submit
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+    /// The year to submit for
+    #[arg(short, long, default_value_t = Utc::now().year())]
+    year: i32,
+}
+
+pub fn submit(args: SubmitArgs, http: HttpArgs) -> anyhow::Result<()> {
+    let http = http.resolve(args.year)?;
+    if http.offline {
+        bail!("cannot submit answers in offline mode");
+    }
+    let session = std::env::var("AOC_SESSION").context("expected AOC_SESSION env var")?;
+    let cache_folder = std::env::var("AOC_CACHE").context("failed to find AOC_CACHE env var")?;
+    let pkg_name = PackageName(args.day);
+
+    let day = DAYS
+        .iter()
+        .find(|day| day.number == args.day)
+        .with_context(|| format!("{pkg_name} is not registered in src/days/mod.rs"))?;
+    let input = std::fs::read_to_string(format!("{cache_folder}/{}_{pkg_name}.txt", args.year))
+        .context("failed to read cached input")?;
+    let result = (day.solve)(&SolveContext::new(&input))
+        .with_context(|| format!("failed to solve {pkg_name}"))?;
+    let answer = match args.part {
+        1 => result.part1,
+        _ => result.part2,
+    }
+    .with_context(|| format!("{pkg_name} part {} has no answer yet", args.part))?;
+    let answer = answer
+        .normalised()
+        .with_context(|| format!("could not read the letters in {pkg_name}'s answer:\n{answer}"))?;
+
+    let mut ledger = Ledger::for_year(args.year)?;
+    ledger
+        .check(args.day, args.part, &answer)
+        .with_context(|| format!("refusing to submit {answer}"))?;
+
+    info!(%answer, "submitting answer");
+    let response = post_answer(&http, args.year, pkg_name, args.part, &answer, &session)?;
+    if let Response::Verdict(verdict) = response {
+        ledger.record(args.day, args.part, answer.clone(), verdict)?;
+        ledger.save()?;
+        info!("recorded verdict to {:?}", ledger.path());
+    }
+
+    println!("{pkg_name} part {}: {answer} - {response}", args.part);
+
+    Ok(())
+}
+
+/// How adventofcode.com responded to a submitted answer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Response {
+    Verdict(Verdict),
+    RateLimited { wait: Duration },
+    AlreadySolved,
+}
+
+impl Display for Response {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Response::Verdict(verdict) => write!(f, "{verdict}"),
+            Response::RateLimited { wait } => write!(f, "rate_limited ({wait:?} left to wait)"),
+            Response::AlreadySolved => write!(f, "already_solved"),
+        }
+    }
+}
+
+static ARTICLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap());
+static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^>]*>").unwrap());
+static WAIT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap());
+
+pub fn parse_response(html: &str) -> anyhow::Result<Response> {
+    let article = ARTICLE
+        .captures(html)
+        .and_then(|captures| captures.get(1))
+        .map(|article| article.as_str())
+        .unwrap_or(html);
+    let text = TAG.replace_all(article, "");
+
+    if text.contains("That's the right answer") {
+        return Ok(Response::Verdict(Verdict::Correct));
+    }
+    if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            return Ok(Response::Verdict(Verdict::TooHigh));
+        }
+        if text.contains("your answer is too low") {
+            return Ok(Response::Verdict(Verdict::TooLow));
+        }
+        return Ok(Response::Verdict(Verdict::Wrong));
+    }
+    if text.contains("You gave an answer too recently") {
+        let wait = WAIT
+            .captures(&text)
+            .map(|captures| {
+                let minutes = captures
+                    .get(1)
+                    .map_or(Ok(0), |m| m.as_str().parse::<u64>())?;
+                let seconds = captures[2].parse::<u64>()?;
+                Ok::<_, std::num::ParseIntError>(Duration::from_secs(minutes * 60 + seconds))
+            })
+            .transpose()
+            .context("failed to parse wait time")?
+            .unwrap_or_default();
+        return Ok(Response::RateLimited { wait });
+    }
+    if text.contains("You don't seem to be solving the right level") {
+        return Ok(Response::AlreadySolved);
+    }
+
+    bail!("unrecognised answer response: {}", text.trim())
+}
+
+fn post_answer(
+    http: &HttpConfig,
+    year: i32,
+    pkg_name: PackageName,
+    part: u8,
+    answer: &Answers,
+    session: &str,
+) -> anyhow::Result<Response> {
+    let url = format!(
+        "{base_url}/{year}/day/{day}/answer",
+        base_url = http.base_url,
+        day = pkg_name.0
+    );
+    info!("posting answer to url {url}");
+
+    let client = http.client()?;
+
+    let response = client
+        .post(url)
+        .header("Cookie", format!("session={session}"))
+        .form(&[("level", part.to_string()), ("answer", answer.to_string())])
+        .send()
+        .context("failed to perform http request")?;
+
+    let status = response.status();
+    let body = response
+        .text()
+        .context("failed to read http response body")?;
+    if !status.is_success() {
+        tracing::error!(
+            status = status.as_u16(),
+            body,
+            "bad http response code returned"
+        );
+        bail!("bad http response code returned: {status}");
+    }
+
+    parse_response(&body)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use advent_of_code_2025::{ledger::Verdict, Answers};
+
+    use super::{parse_response, post_answer, Response};
+    use crate::{http::HttpConfig, test_server, PackageName};
+
+    fn page(message: &str) -> String {
+        format!("<html><body><main>\n<article><p>{message}</p></article>\n</main></body></html>")
+    }
+
+    #[test]
+    fn parses_responses() {
+        let cases = [
+            (
+                "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.",
+                Response::Verdict(Verdict::Correct),
+            ),
+            (
+                "That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.",
+                Response::Verdict(Verdict::TooHigh),
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Response::Verdict(Verdict::TooLow),
+            ),
+            (
+                "That's not the right answer.  If you're stuck, make sure you're using the full input data.",
+                Response::Verdict(Verdict::Wrong),
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 49s left to wait.",
+                Response::RateLimited {
+                    wait: Duration::from_secs(289),
+                },
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 38s left to wait.",
+                Response::RateLimited {
+                    wait: Duration::from_secs(38),
+                },
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                Response::AlreadySolved,
+            ),
+        ];
+
+        for (message, expected) in cases {
+            assert_eq!(
+                expected,
+                parse_response(&page(message)).unwrap(),
+                "{message}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_responses() {
+        assert!(parse_response(&page("Something new happened")).is_err());
+    }
+
+    #[test]
+    fn posts_to_local_server() {
+        let (base_url, server) = test_server::serve_once(
+            200,
+            page("That's not the right answer; your answer is too low."),
+        );
+
+        let response = post_answer(
+            &HttpConfig::for_test(base_url),
+            2025,
+            PackageName(3),
+            2,
+            &Answers::from(1234_u64),
+            "secret",
+        )
+        .unwrap();
+        assert_eq!(Response::Verdict(Verdict::TooLow), response);
+
+        let request = server.join().unwrap();
+        assert_eq!("POST /2025/day/3/answer HTTP/1.1", request.request_line);
+        assert_eq!("session=secret", request.cookie);
+        assert_eq!("level=2&answer=1234", request.body);
+    }
+
+    #[test]
+    fn fails_on_bad_status() {
+        let (base_url, server) = test_server::serve_once(500, "oops".to_string());
+
+        let err = post_answer(
+            &HttpConfig::for_test(base_url),
+            2025,
+            PackageName(3),
+            2,
+            &Answers::from(1234_u64),
+            "secret",
+        )
+        .unwrap_err();
+        assert_eq!(
+            "bad http response code returned: 500 Internal Server Error",
+            err.to_string()
+        );
+        server.join().unwrap();
+    }
+}
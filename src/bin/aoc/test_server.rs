@@ -0,0 +1,68 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    thread::JoinHandle,
+};
+
+/// What the stub server saw of the single request it served.
+#[derive(Debug)]
+pub struct Request {
+    pub request_line: String,
+    pub cookie: String,
+    pub user_agent: String,
+    pub body: String,
+}
+
+// Shared with the integration tests in `tests/aoc.rs`, so this must not depend on the
+// rest of the `aoc` binary.
+
+/// Serves exactly one http request with the given status and body, returning the
+/// base url to point the client at and a handle yielding the request received.
+pub fn serve_once(status: u16, body: String) -> (String, JoinHandle<Request>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let server = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+
+        let mut content_length = 0;
+        let mut cookie = String::new();
+        let mut user_agent = String::new();
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            let (name, value) = header.split_once(": ").unwrap();
+            match name.to_ascii_lowercase().as_str() {
+                "content-length" => content_length = value.parse().unwrap(),
+                "cookie" => cookie = value.to_string(),
+                "user-agent" => user_agent = value.to_string(),
+                _ => {}
+            }
+        }
+        let mut request_body = vec![0; content_length];
+        reader.read_exact(&mut request_body).unwrap();
+
+        write!(
+            reader.get_mut(),
+            "HTTP/1.1 {status} STUB\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+
+        Request {
+            request_line: request_line.trim_end().to_string(),
+            cookie,
+            user_agent,
+            body: String::from_utf8(request_body).unwrap(),
+        }
+    });
+
+    (base_url, server)
+}
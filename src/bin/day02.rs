@@ -1 +0,0 @@
-advent_of_code_2025::aoc!(day02);
@@ -1 +0,0 @@
-advent_of_code_2025::aoc!(day04);
\ No newline at end of file
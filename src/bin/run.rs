@@ -0,0 +1,359 @@
+use std::{
+    fmt::{Display, Formatter},
+    ops::RangeInclusive,
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use advent_of_code_2025::{
+    days::DAYS, get_input, parse::ParseError, Answers, Day, DayResult, Params, Phase, SolveContext,
+};
+use anyhow::{bail, Context};
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let is_test = std::env::var_os("TEST").is_some() || args.test;
+
+    let days = args.days.select(DAYS)?;
+    let mut rows = Vec::with_capacity(days.len());
+    let mut solutions = Vec::with_capacity(days.len());
+    for day in days {
+        let input = get_input(day.name, is_test)
+            .with_context(|| format!("failed to get input for {}", day.name))?;
+
+        let mut runs = Vec::with_capacity(args.repeat as usize);
+        let mut solution = None;
+        for _ in 0..args.repeat {
+            let ctx = SolveContext::new(&input)
+                .with_test(is_test)
+                .with_params(args.params.clone())
+                .with_visualize(args.visualize.clone());
+            let start = Instant::now();
+            let result = (day.solve)(&ctx).inspect_err(|err| {
+                if let Some(err) = err.downcast_ref::<ParseError>() {
+                    eprintln!("{}", err.render());
+                }
+            });
+            let result = result.with_context(|| format!("failed to solve {}", day.name))?;
+            let total = start.elapsed();
+            runs.push(Run {
+                total,
+                phases: ctx.take_timings(),
+            });
+            solution = Some(result);
+        }
+
+        if let Some(solution) = solution {
+            if args.format == Format::Plain {
+                println!("{name}: {solution}", name = day.name);
+            }
+            solutions.push(solution);
+        }
+        rows.push(Row::new(day.name, &runs));
+    }
+
+    match args.format {
+        Format::Plain => print!("{}", Table::new(&rows, args.repeat > 1)),
+        Format::Json => {
+            let records = records(&rows, &solutions).collect::<Vec<_>>();
+            serde_json::to_writer_pretty(std::io::stdout().lock(), &records)
+                .context("failed to write json")?;
+            println!();
+        }
+        Format::Csv | Format::Tsv => {
+            let delimiter = if args.format == Format::Csv {
+                b','
+            } else {
+                b'\t'
+            };
+            let mut writer = csv::WriterBuilder::new()
+                .delimiter(delimiter)
+                .from_writer(std::io::stdout().lock());
+            for record in records(&rows, &solutions) {
+                writer.serialize(record).context("failed to write record")?;
+            }
+            writer.flush().context("failed to flush output")?;
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Days to run: a single day (`3`), a range (`1..=4` or `1..5`) or `all`
+    #[arg(default_value = "all")]
+    days: DaySelection,
+    /// Use the example input from `test_input` instead of the real input
+    #[arg(short, long, default_value_t = false)]
+    test: bool,
+    /// Extra solver parameters as comma separated `key=value` pairs
+    #[arg(short, long, default_value = "")]
+    params: Params,
+    /// Directory for solvers that can draw their progress to write it to
+    #[arg(long)]
+    visualize: Option<PathBuf>,
+    /// Run each day this many times and report the min / median / max
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    repeat: u16,
+    /// How to print results: `plain` for people, or `json`, `csv` and `tsv` for scripts
+    #[arg(short, long, value_enum, default_value_t = Format::Plain)]
+    format: Format,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Plain,
+    Json,
+    Csv,
+    Tsv,
+}
+
+#[derive(Clone, Debug)]
+enum DaySelection {
+    All,
+    Range(RangeInclusive<u32>),
+}
+
+impl DaySelection {
+    fn select(&self, days: &'static [Day]) -> anyhow::Result<Vec<&'static Day>> {
+        let selected = days
+            .iter()
+            .filter(|day| match self {
+                DaySelection::All => true,
+                DaySelection::Range(range) => range.contains(&day.number),
+            })
+            .collect::<Vec<_>>();
+        if selected.is_empty() {
+            bail!("no registered days match {self:?}");
+        }
+
+        Ok(selected)
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(DaySelection::All);
+        }
+
+        let parse_day = |s: &str| {
+            s.trim()
+                .parse::<u32>()
+                .with_context(|| format!("invalid day number {s:?}"))
+        };
+
+        let range = if let Some((start, end)) = s.split_once("..=") {
+            parse_day(start)?..=parse_day(end)?
+        } else if let Some((start, end)) = s.split_once("..") {
+            let end = parse_day(end)?;
+            if end == 0 {
+                bail!("empty day range {s:?}");
+            }
+            parse_day(start)?..=end - 1
+        } else {
+            let day = parse_day(s)?;
+            day..=day
+        };
+
+        if range.is_empty() {
+            bail!("empty day range {s:?}");
+        }
+
+        Ok(DaySelection::Range(range))
+    }
+}
+
+struct Run {
+    total: Duration,
+    phases: Vec<(Phase, Duration)>,
+}
+
+#[derive(Copy, Clone)]
+struct Stats {
+    min: Duration,
+    median: Duration,
+    max: Duration,
+}
+
+impl Stats {
+    fn new(mut samples: Vec<Duration>) -> Option<Self> {
+        samples.sort_unstable();
+        Some(Self {
+            min: *samples.first()?,
+            median: samples[(samples.len() - 1) / 2],
+            max: *samples.last()?,
+        })
+    }
+}
+
+impl std::ops::Add for Stats {
+    type Output = Stats;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Stats {
+            min: self.min + rhs.min,
+            median: self.median + rhs.median,
+            max: self.max + rhs.max,
+        }
+    }
+}
+
+struct Row {
+    name: &'static str,
+    phases: [Option<Stats>; 3],
+    total: Stats,
+}
+
+impl Row {
+    fn new(name: &'static str, runs: &[Run]) -> Self {
+        let phase = |phase| {
+            let samples = runs
+                .iter()
+                .map(|run| {
+                    run.phases
+                        .iter()
+                        .filter(|&&(p, _)| p == phase)
+                        .map(|&(_, elapsed)| elapsed)
+                        .reduce(|a, b| a + b)
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Stats::new(samples)
+        };
+
+        Self {
+            name,
+            phases: [
+                phase(Phase::Parse),
+                phase(Phase::Part1),
+                phase(Phase::Part2),
+            ],
+            total: Stats::new(runs.iter().map(|run| run.total).collect())
+                .expect("every day runs at least once"),
+        }
+    }
+}
+
+/// The timing summary printed after every selected day has run. Phase columns show the
+/// median and are blank for solvers that do not time their phases.
+struct Table {
+    cells: Vec<Vec<String>>,
+}
+
+impl Table {
+    fn new(rows: &[Row], repeated: bool) -> Self {
+        let mut header = vec!["day", "parse", "part 1", "part 2"];
+        if repeated {
+            header.extend(["min", "median", "max"]);
+        } else {
+            header.push("total");
+        }
+
+        let format = |d: Duration| format!("{d:.2?}");
+        let total_cells = |stats: Stats| {
+            if repeated {
+                vec![format(stats.min), format(stats.median), format(stats.max)]
+            } else {
+                vec![format(stats.median)]
+            }
+        };
+
+        let mut cells = vec![header.into_iter().map(str::to_string).collect::<Vec<_>>()];
+        for row in rows {
+            let mut line = vec![row.name.to_string()];
+            line.extend(
+                row.phases
+                    .iter()
+                    .map(|stats| stats.map_or("-".to_string(), |s| format(s.median))),
+            );
+            line.extend(total_cells(row.total));
+            cells.push(line);
+        }
+
+        if let Some(total) = rows.iter().map(|row| row.total).reduce(|a, b| a + b) {
+            let mut line = vec![
+                "total".to_string(),
+                String::new(),
+                String::new(),
+                String::new(),
+            ];
+            line.extend(total_cells(total));
+            cells.push(line);
+        }
+
+        Self { cells }
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let columns = self.cells[0].len();
+        let widths = (0..columns)
+            .map(|i| {
+                self.cells
+                    .iter()
+                    .map(|line| line[i].len())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect::<Vec<_>>();
+
+        for line in &self.cells {
+            for (i, (cell, width)) in line.iter().zip(&widths).enumerate() {
+                if i == 0 {
+                    write!(f, "{cell:<width$}")?;
+                } else {
+                    write!(f, "  {cell:>width$}")?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// One row of machine-readable output, describing a single part of a day. Timings are
+/// in nanoseconds and shared by both parts of a day; phase timings are the median and
+/// are only present for solvers that time their phases.
+#[derive(Serialize)]
+struct Record {
+    day: &'static str,
+    part: u8,
+    answer: Option<String>,
+    answer_type: Option<&'static str>,
+    parse_ns: Option<u64>,
+    part1_ns: Option<u64>,
+    part2_ns: Option<u64>,
+    total_ns: u64,
+    total_min_ns: u64,
+    total_max_ns: u64,
+}
+
+fn records<'a>(rows: &'a [Row], solutions: &'a [DayResult]) -> impl Iterator<Item = Record> + 'a {
+    let nanos = |d: Duration| d.as_nanos() as u64;
+    rows.iter().zip(solutions).flat_map(move |(row, solution)| {
+        let [parse, part1, part2] = row.phases.map(|stats| stats.map(|s| nanos(s.median)));
+        [(1, &solution.part1), (2, &solution.part2)]
+            .into_iter()
+            .map(move |(part, answer): (u8, &Option<Answers>)| Record {
+                day: row.name,
+                part,
+                answer: answer.as_ref().map(ToString::to_string),
+                answer_type: answer.as_ref().map(Answers::kind),
+                parse_ns: parse,
+                part1_ns: part1,
+                part2_ns: part2,
+                total_ns: nanos(row.total.median),
+                total_min_ns: nanos(row.total.min),
+                total_max_ns: nanos(row.total.max),
+            })
+    })
+}
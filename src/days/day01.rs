@@ -1,36 +1,32 @@
-use crate::{DayResult, IntoDayResult};
+use crate::{
+    parse::{self, ParseError},
+    DayResult, IntoDayResult, SolveContext,
+};
 use anyhow::Result;
 
-pub fn solve(input: &str) -> Result<DayResult> {
+pub fn solve(ctx: &SolveContext<'_>) -> Result<DayResult> {
+    let input = ctx.input.as_bytes();
     let mut p1 = 0;
     let mut p2 = 0;
 
     let mut pos: i32 = 50;
-    let mut dir: i32 = -1;
-    let mut number: i32 = 0;
-    let mut has_number = false;
+    // each turn is `L` or `R` and a number of steps on its own line
+    let mut rest = input.trim_ascii_end();
+    while let [dir, tail @ ..] = rest {
+        let dir = match dir {
+            b'L' => -1,
+            b'R' => 1,
+            _ => return Err(ParseError::at(input, rest, "'L' or 'R'").into()),
+        };
+        let (steps, len) = parse::unsigned::<i32>(tail)
+            .ok_or_else(|| ParseError::at(input, tail, "a number of steps"))?;
+        apply_turn(&mut pos, dir, steps, &mut p1, &mut p2);
 
-    for &b in input.as_bytes() {
-        match b {
-            b'L' => dir = -1,
-            b'R' => dir = 1,
-            b'\n' => {
-                if has_number {
-                    apply_turn(&mut pos, dir, number, &mut p1, &mut p2);
-                    number = 0;
-                    has_number = false;
-                }
-            }
-            b'0'..=b'9' => {
-                number = number * 10 + (b - b'0') as i32;
-                has_number = true;
-            }
-            _ => {}
-        }
-    }
-
-    if has_number {
-        apply_turn(&mut pos, dir, number, &mut p1, &mut p2);
+        rest = match &tail[len..] {
+            [] => &[],
+            [b'\n', next @ ..] | [b'\r', b'\n', next @ ..] => next,
+            other => return Err(ParseError::at(input, other, "a digit or the end of the line").into()),
+        };
     }
 
     (p1, p2).into_result()
@@ -67,20 +63,37 @@ fn apply_turn(pos: &mut i32, dir: i32, steps: i32, p1: &mut i32, p2: &mut i32) {
 
 #[cfg(test)]
 mod tests {
-    use crate::{days::day01::solve, IntoDayResult};
+    use crate::{days::day01::solve, parse::ParseError, IntoDayResult, SolveContext};
 
     #[test]
     fn works_for_example() {
         const INPUT: &str = include_str!("../../test_input/day01.txt");
-        let solution = solve(INPUT).unwrap();
+        let solution = solve(&SolveContext::new(INPUT).with_test(true)).unwrap();
         assert_eq!((3, 6).into_day_result(), solution);
     }
 
     #[test]
-    fn works_for_input() {
-        const INPUT: &str =
-            include_str!(concat!(std::env!("AOC_CACHE"), "/2025_", "day01", ".txt"));
-        let solution = solve(INPUT).unwrap();
-        assert_eq!((1_177, 6_768).into_day_result(), solution);
+    fn rejects_malformed_turns() {
+        let err = solve(&SolveContext::new("L68\nR30\nU5\n")).unwrap_err();
+        assert_eq!(
+            "line 3, column 1: expected 'L' or 'R', found 'U'",
+            err.to_string()
+        );
+        let err = solve(&SolveContext::new("L68\r\nR3x\n")).unwrap_err();
+        let err = err.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((2, 3), (err.line, err.column));
+        assert!(solve(&SolveContext::new("L\n")).is_err());
+
+        // only trailing whitespace is allowed, blank lines and indents used to be skipped
+        let err = solve(&SolveContext::new("L68\n\nR30\n")).unwrap_err();
+        assert_eq!(
+            "line 2, column 1: expected 'L' or 'R', found the end of the line",
+            err.to_string()
+        );
+        let err = solve(&SolveContext::new(" L68\n")).unwrap_err();
+        assert_eq!(
+            "line 1, column 1: expected 'L' or 'R', found ' '",
+            err.to_string()
+        );
     }
 }
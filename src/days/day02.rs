@@ -1,17 +1,17 @@
-use crate::{DayResult, IntoDayResult};
-use anyhow::{Context, Result};
+use crate::{
+    parse::combinators::{comma_list, finish, range},
+    DayResult, IntoDayResult, SolveContext,
+};
+use anyhow::Result;
 
-pub fn solve(_input: &str) -> Result<DayResult> {
-    let input = _input.trim();
+pub fn solve(ctx: &SolveContext<'_>) -> Result<DayResult> {
+    let ranges = finish(comma_list(range::<u64>), ctx.input)?;
 
     let mut p1 = 0;
     let mut p2 = 0;
     let mut buf = itoa::Buffer::new();
 
-    for ids in input.split(',') {
-        let (a_str, b_str) = ids.split_once('-').context("no hyphen found")?;
-        let a = a_str.parse::<u64>().context("failed to parse a")?;
-        let b = b_str.parse::<u64>().context("failed to parse b")?;
+    for (a, b) in ranges {
         let mut id = a;
         while id <= b {
             let id_bytes = buf.format(id).as_bytes();
@@ -131,12 +131,12 @@ const LEN_20: [usize; 5] = [20, 10, 5, 4, 2];
 
 #[cfg(test)]
 mod tests {
-    use crate::{days::day02::solve, IntoDayResult};
+    use crate::{days::day02::solve, IntoDayResult, SolveContext};
 
     #[test]
     fn works_for_example() {
         const INPUT: &str = include_str!("../../test_input/day02.txt");
-        let solution = solve(INPUT).unwrap();
+        let solution = solve(&SolveContext::new(INPUT).with_test(true)).unwrap();
         assert_eq!(
             (1_227_775_554_usize, 4_174_379_265_usize).into_day_result(),
             solution
@@ -144,13 +144,11 @@ mod tests {
     }
 
     #[test]
-    fn works_for_input() {
-        const INPUT: &str =
-            include_str!(concat!(std::env!("AOC_CACHE"), "/2025_", "day02", ".txt"));
-        let solution = solve(INPUT).unwrap();
+    fn rejects_malformed_ranges() {
+        let err = solve(&SolveContext::new("11-22,95_115\n")).unwrap_err();
         assert_eq!(
-            (32_976_912_643_usize, 54_446_379_122_usize).into_day_result(),
-            solution
+            "line 1, column 9: expected a separator, found '_'",
+            err.to_string()
         );
     }
 }
@@ -0,0 +1,352 @@
+use crate::{
+    parse::{ParseError, RowLayout},
+    DayResult, IntoDayResult, Phase, SolveContext,
+};
+use anyhow::Result;
+use std::ptr;
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod simd;
+
+/// Part 2 picks this many digits from each bank.
+const PICKED: usize = 12;
+
+/// Which `best_twelve` kernel to use, e.g. `run 3 --params best_twelve=simple` to
+/// compare them on the real input. Banks of a width without a specialised kernel use
+/// `window` unless `simd` is picked, which also replaces `best_two` for part 1. `simd`
+/// needs the `simd` feature and is never picked by `auto`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, strum::EnumString)]
+#[strum(serialize_all = "snake_case")]
+enum BestTwelve {
+    #[default]
+    Auto,
+    Simple,
+    TailCopy,
+    Window,
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    Simd,
+}
+
+pub fn solve(ctx: &SolveContext<'_>) -> Result<DayResult> {
+    let input = ctx.input.as_bytes().trim_ascii_end();
+    let kernel = ctx.params.get("best_twelve")?.unwrap_or_default();
+    let layout = ctx.time(Phase::Parse, || parse(input))?;
+
+    let (p1, p2) = match layout.width() {
+        15 => solve_with_kernel::<15>(input, layout, kernel),
+        100 => solve_with_kernel::<100>(input, layout, kernel),
+        _ => solve_any_width(input, layout, kernel),
+    };
+
+    (p1, p2).into_result()
+}
+
+/// Finds how wide the banks are and checks every bank is that wide and all digits, which
+/// the kernels rely on.
+fn parse(input: &[u8]) -> Result<RowLayout, ParseError> {
+    let layout = RowLayout::detect(input)
+        .ok_or_else(|| ParseError::new(input, 0, "a bank of digits"))?;
+    if layout.width() < PICKED {
+        return Err(ParseError::new(
+            input,
+            layout.width(),
+            format!("a digit, as banks need at least {PICKED}"),
+        ));
+    }
+    layout.validate(input, |b| b.is_ascii_digit(), "a digit")?;
+    Ok(layout)
+}
+
+fn solve_with_kernel<const WIDTH: usize>(
+    input: &[u8],
+    layout: RowLayout,
+    kernel: BestTwelve,
+) -> (usize, usize) {
+    debug_assert_eq!(WIDTH, layout.width());
+    match kernel {
+        BestTwelve::Auto => solve_impl(input, layout, best_two, best_twelve::<WIDTH>),
+        BestTwelve::Simple => solve_impl(input, layout, best_two, best_twelve_simple::<WIDTH>),
+        BestTwelve::TailCopy => {
+            solve_impl(input, layout, best_two, best_twelve_tail_copy::<WIDTH>)
+        }
+        _ => solve_any_width(input, layout, kernel),
+    }
+}
+
+fn solve_any_width(input: &[u8], layout: RowLayout, kernel: BestTwelve) -> (usize, usize) {
+    match kernel {
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        BestTwelve::Simd => {
+            let max_first = simd::max_first();
+            solve_impl(
+                input,
+                layout,
+                |digits| best_window(digits, 2, max_first),
+                |digits| best_window(digits, PICKED, max_first),
+            )
+        }
+        _ => solve_impl(input, layout, best_two, best_twelve_window),
+    }
+}
+
+#[inline(always)]
+fn solve_impl(
+    input: &[u8],
+    layout: RowLayout,
+    best_two: impl Fn(&[u8]) -> u64,
+    best_twelve: impl Fn(&[u8]) -> u64,
+) -> (usize, usize) {
+    let mut p1 = 0;
+    let mut p2 = 0;
+    for digits in layout.rows(input) {
+        p1 += best_two(digits) as usize;
+        p2 += best_twelve(digits) as usize;
+    }
+    (p1, p2)
+}
+
+fn best_two(digits: &[u8]) -> u64 {
+    // Track the best possible second digit while scanning from right to left so we only
+    // touch the slice once and still respect ordering constraints.
+    let n = digits.len();
+    let mut best_suffix = unsafe { *digits.get_unchecked(n - 1) };
+    let mut best_pair = 0u64;
+
+    for i in (0..n - 1).rev() {
+        let d1 = unsafe { *digits.get_unchecked(i) };
+        let candidate = ((d1 - b'0') as u64) * 10 + ((best_suffix - b'0') as u64);
+        if candidate > best_pair {
+            best_pair = candidate;
+            if best_pair == 99 {
+                break;
+            }
+        }
+        if d1 > best_suffix {
+            best_suffix = d1;
+        }
+    }
+
+    best_pair
+}
+
+fn best_twelve<const MAX_DIGITS: usize>(digits: &[u8]) -> u64 {
+    if MAX_DIGITS <= 24 {
+        return best_twelve_simple::<MAX_DIGITS>(digits);
+    }
+    best_twelve_tail_copy::<MAX_DIGITS>(digits)
+}
+
+#[inline(always)]
+fn best_twelve_simple<const MAX_DIGITS: usize>(digits: &[u8]) -> u64 {
+    let mut stack = [0u8; MAX_DIGITS];
+    let mut len = 0;
+    let mut to_remove = digits.len() - PICKED;
+    for &digit in digits {
+        while to_remove > 0 && len > 0 && digit > unsafe { *stack.get_unchecked(len - 1) } {
+            len -= 1;
+            to_remove -= 1;
+        }
+        unsafe { *stack.get_unchecked_mut(len) = digit };
+        len += 1;
+    }
+
+    digits12_to_u64(&stack)
+}
+
+#[inline(always)]
+fn best_twelve_tail_copy<const MAX_DIGITS: usize>(digits: &[u8]) -> u64 {
+    let mut stack = [0u8; MAX_DIGITS];
+    let mut len = 0;
+    let mut to_remove = digits.len() - PICKED;
+    let mut idx = 0;
+
+    while idx < digits.len() {
+        let digit = unsafe { *digits.get_unchecked(idx) };
+        idx += 1;
+
+        while to_remove > 0 && len > 0 && digit > unsafe { *stack.get_unchecked(len - 1) } {
+            len -= 1;
+            to_remove -= 1;
+        }
+
+        unsafe { *stack.get_unchecked_mut(len) = digit };
+        len += 1;
+
+        if to_remove == 0 {
+            let remaining = digits.len() - idx;
+            if remaining > 0 {
+                unsafe {
+                    ptr::copy_nonoverlapping(
+                        digits.get_unchecked(idx),
+                        stack.get_unchecked_mut(len),
+                        remaining,
+                    );
+                }
+            }
+            break;
+        }
+    }
+
+    digits12_to_u64(&stack)
+}
+
+fn best_twelve_window(digits: &[u8]) -> u64 {
+    best_window(digits, PICKED, max_first)
+}
+
+/// Picks each digit greedily as the first largest digit that still leaves enough after
+/// it for the rest. Works for banks of any width, without the unchecked stack the
+/// specialised kernels use.
+#[inline(always)]
+fn best_window(digits: &[u8], picked: usize, max_first: impl Fn(&[u8]) -> (usize, u8)) -> u64 {
+    let mut res = 0;
+    let mut start = 0;
+    for left in (0..picked).rev() {
+        let window = &digits[start..digits.len() - left];
+        let (offset, digit) = max_first(window);
+        res = res * 10 + (digit - b'0') as u64;
+        start += offset + 1;
+    }
+    res
+}
+
+/// The position and value of the first largest digit in a non-empty window of digits.
+#[inline(always)]
+fn max_first(window: &[u8]) -> (usize, u8) {
+    let mut best = (0, 0);
+    for (i, &b) in window.iter().enumerate() {
+        if b > best.1 {
+            best = (i, b);
+            if b == b'9' {
+                break;
+            }
+        }
+    }
+    best
+}
+
+#[inline(always)]
+fn digits12_to_u64(buf: &[u8]) -> u64 {
+    let mut res = 0u64;
+    for i in 0..PICKED {
+        res = res * 10 + unsafe { (*buf.get_unchecked(i) - b'0') as u64 };
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        days::day03::{
+            best_twelve, best_twelve_simple, best_twelve_tail_copy, best_twelve_window, best_two,
+            solve,
+        },
+        subsequence::{best_subsequence, Extreme},
+        IntoDayResult, SolveContext,
+    };
+    use proptest::prelude::*;
+
+    #[test]
+    fn works_for_example() {
+        const INPUT: &str = include_str!("../../../test_input/day03.txt");
+        let solution = solve(&SolveContext::new(INPUT).with_test(true)).unwrap();
+        assert_eq!((357, 3_121_910_778_619_usize).into_day_result(), solution);
+    }
+
+    #[test]
+    fn kernels_agree_on_example() {
+        const INPUT: &str = include_str!("../../../test_input/day03.txt");
+        let kernels = ["auto", "simple", "tail_copy", "window"];
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        let kernels = [&kernels[..], &["simd"]].concat();
+        for kernel in kernels {
+            let ctx = SolveContext::new(INPUT)
+                .with_test(true)
+                .with_param("best_twelve", kernel);
+            assert_eq!(
+                (357, 3_121_910_778_619_usize).into_day_result(),
+                solve(&ctx).unwrap(),
+                "{kernel}"
+            );
+        }
+    }
+
+    #[test]
+    fn detects_width_and_line_endings() {
+        const INPUT: &str = include_str!("../../../test_input/day03.txt");
+        let crlf = INPUT.replace("\r", "").replace('\n', "\r\n");
+        assert_eq!(
+            (357, 3_121_910_778_619_usize).into_day_result(),
+            solve(&SolveContext::new(&crlf)).unwrap()
+        );
+
+        // 13 digit banks have no specialised kernel; picking 12 of them is dropping one
+        let narrow = INPUT.lines().map(|line| &line[..13]).collect::<Vec<_>>();
+        let expected = narrow
+            .iter()
+            .map(|bank| {
+                (0..13)
+                    .map(|skip| format!("{}{}", &bank[..skip], &bank[skip + 1..]))
+                    .map(|picked| picked.parse::<usize>().unwrap())
+                    .max()
+                    .unwrap()
+            })
+            .sum::<usize>();
+        let solution = solve(&SolveContext::new(&narrow.join("\n"))).unwrap();
+        assert_eq!(Some(expected.into()), solution.part2);
+    }
+
+    #[test]
+    fn rejects_malformed_banks() {
+        let err = solve(&SolveContext::new("1234567890123\n123456789012\n")).unwrap_err();
+        assert_eq!(
+            "line 2, column 13: expected a digit, as rows are 13 wide, found the end of the line",
+            err.to_string()
+        );
+        let err = solve(&SolveContext::new("123456789012x\n")).unwrap_err();
+        assert_eq!(
+            "line 1, column 13: expected a digit, found 'x'",
+            err.to_string()
+        );
+        assert!(solve(&SolveContext::new("12345\n12345\n")).is_err());
+        assert!(solve(&SolveContext::new("")).is_err());
+    }
+
+    fn largest(bank: &[u8], k: usize) -> u64 {
+        best_subsequence(bank, k, Extreme::Largest)
+            .and_then(|picked| picked.to_u64())
+            .unwrap()
+    }
+
+    proptest! {
+        #[test]
+        fn kernels_match_best_subsequence(
+            narrow in "[0-9]{15}",
+            wide in "[0-9]{100}",
+            other in "[0-9]{12,40}",
+        ) {
+            let narrow = narrow.as_bytes();
+            prop_assert_eq!(largest(narrow, 2), best_two(narrow));
+            prop_assert_eq!(largest(narrow, 12), best_twelve::<15>(narrow));
+            prop_assert_eq!(largest(narrow, 12), best_twelve_simple::<15>(narrow));
+            prop_assert_eq!(largest(narrow, 12), best_twelve_tail_copy::<15>(narrow));
+
+            let wide = wide.as_bytes();
+            prop_assert_eq!(largest(wide, 2), best_two(wide));
+            prop_assert_eq!(largest(wide, 12), best_twelve::<100>(wide));
+            prop_assert_eq!(largest(wide, 12), best_twelve_simple::<100>(wide));
+            prop_assert_eq!(largest(wide, 12), best_twelve_tail_copy::<100>(wide));
+
+            let other = other.as_bytes();
+            prop_assert_eq!(largest(other, 12), best_twelve_window(other));
+
+            #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+            for bank in [narrow, wide, other] {
+                use crate::days::day03::{best_window, simd};
+                let max_first = simd::max_first();
+                prop_assert_eq!(largest(bank, 2), best_window(bank, 2, max_first));
+                prop_assert_eq!(largest(bank, 12), best_window(bank, 12, max_first));
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! [`super::max_first`] with AVX2 or SSE2, picked at runtime by [`max_first`].
+//!
+//! Whole vectors are compared against the largest digit so far, and only a vector that
+//! holds something larger has its largest digit and where it first is worked out. The
+//! largest digit only goes up, at most ten times, so nearly every vector is a single
+//! compare, and a nine ends the scan early.
+use std::arch::x86_64::{
+    __m128i, __m256i, _mm256_castsi256_si128, _mm256_cmpeq_epi8, _mm256_extracti128_si256,
+    _mm256_loadu_si256, _mm256_max_epu8, _mm256_movemask_epi8, _mm256_set1_epi8,
+    _mm_cmpeq_epi8, _mm_cvtsi128_si32, _mm_loadu_si128, _mm_max_epu8, _mm_movemask_epi8,
+    _mm_set1_epi8, _mm_srli_si128,
+};
+
+/// The fastest way to find the first largest digit in a non-empty window of digits
+/// that the CPU supports. Detecting this is an atomic load, so look it up once per
+/// input rather than once per window.
+pub fn max_first() -> fn(&[u8]) -> (usize, u8) {
+    if is_x86_feature_detected!("avx2") {
+        avx2
+    } else {
+        sse2
+    }
+}
+
+/// Only handed out by [`max_first`] once AVX2 has been detected.
+fn avx2(window: &[u8]) -> (usize, u8) {
+    // SAFETY: the CPU has AVX2
+    unsafe { max_first_avx2(window) }
+}
+
+fn sse2(window: &[u8]) -> (usize, u8) {
+    // SAFETY: SSE2 is part of x86_64
+    unsafe { max_first_sse2(window, 0, (0, 0)) }
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn max_first_avx2(window: &[u8]) -> (usize, u8) {
+    let mut best = (0, 0);
+    let mut start = 0;
+    while start + 32 <= window.len() {
+        // SAFETY: there are 32 bytes from `start`, and loadu has no alignment requirement
+        let bytes = unsafe { _mm256_loadu_si256(window.as_ptr().add(start).cast::<__m256i>()) };
+        let floor = _mm256_set1_epi8(best.1 as i8);
+        // bytes no larger than the best so far are left alone by the max
+        let no_larger = _mm256_cmpeq_epi8(_mm256_max_epu8(bytes, floor), floor);
+        if _mm256_movemask_epi8(no_larger) != -1 {
+            let halves = _mm_max_epu8(
+                _mm256_castsi256_si128(bytes),
+                _mm256_extracti128_si256::<1>(bytes),
+            );
+            let digit = horizontal_max(halves);
+            let at = _mm256_movemask_epi8(_mm256_cmpeq_epi8(bytes, _mm256_set1_epi8(digit as i8)));
+            best = (start + at.trailing_zeros() as usize, digit);
+            if digit == b'9' {
+                return best;
+            }
+        }
+        start += 32;
+    }
+    // SAFETY: AVX2 implies SSE2
+    unsafe { max_first_sse2(window, start, best) }
+}
+
+/// Carries on from `start` with the best digit before it. SSE2 is part of x86_64, so
+/// this needs no detection.
+#[target_feature(enable = "sse2")]
+unsafe fn max_first_sse2(window: &[u8], mut start: usize, mut best: (usize, u8)) -> (usize, u8) {
+    while start + 16 <= window.len() {
+        // SAFETY: there are 16 bytes from `start`, and loadu has no alignment requirement
+        let bytes = unsafe { _mm_loadu_si128(window.as_ptr().add(start).cast::<__m128i>()) };
+        let floor = _mm_set1_epi8(best.1 as i8);
+        let no_larger = _mm_cmpeq_epi8(_mm_max_epu8(bytes, floor), floor);
+        if _mm_movemask_epi8(no_larger) != 0xffff {
+            let digit = horizontal_max(bytes);
+            let at = _mm_movemask_epi8(_mm_cmpeq_epi8(bytes, _mm_set1_epi8(digit as i8)));
+            best = (start + at.trailing_zeros() as usize, digit);
+            if digit == b'9' {
+                return best;
+            }
+        }
+        start += 16;
+    }
+
+    // too few bytes left for a vector
+    if start < window.len() {
+        let (offset, digit) = super::max_first(&window[start..]);
+        if digit > best.1 {
+            best = (start + offset, digit);
+        }
+    }
+    best
+}
+
+/// The largest of 16 bytes, by folding the top half of what is left onto the bottom.
+#[target_feature(enable = "sse2")]
+fn horizontal_max(bytes: __m128i) -> u8 {
+    let bytes = _mm_max_epu8(bytes, _mm_srli_si128::<8>(bytes));
+    let bytes = _mm_max_epu8(bytes, _mm_srli_si128::<4>(bytes));
+    let bytes = _mm_max_epu8(bytes, _mm_srli_si128::<2>(bytes));
+    let bytes = _mm_max_epu8(bytes, _mm_srli_si128::<1>(bytes));
+    _mm_cvtsi128_si32(bytes) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::day03::simd::{max_first_avx2, max_first_sse2};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn matches_scalar(digits in "[0-9]{1,200}", no_nines in "[0-8]{1,200}") {
+            for window in [digits.as_bytes(), no_nines.as_bytes()] {
+                let expected = crate::days::day03::max_first(window);
+                // SAFETY: SSE2 is part of x86_64
+                prop_assert_eq!(expected, unsafe { max_first_sse2(window, 0, (0, 0)) });
+                if is_x86_feature_detected!("avx2") {
+                    // SAFETY: the CPU has AVX2
+                    prop_assert_eq!(expected, unsafe { max_first_avx2(window) });
+                }
+            }
+        }
+    }
+}
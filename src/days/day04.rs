@@ -1,235 +1,150 @@
-use std::collections::VecDeque;
-use std::ops::{Index, IndexMut};
-
-use crate::{DayResult, IntoDayResult};
+use crate::{
+    graph::{KCore, Peeling},
+    grid::{Grid, GridLike},
+    parse::ParseError,
+    DayResult, IntoDayResult, Phase, SolveContext,
+};
 use anyhow::Result;
 
-const NEIGHBOR_OFFSETS: [(isize, isize); 8] = [
-    (-1, -1),
-    (-1, 0),
-    (-1, 1),
-    (0, -1),
-    (0, 1),
-    (1, -1),
-    (1, 0),
-    (1, 1),
-];
-
-pub fn solve(_input: &str) -> Result<DayResult> {
-    let bytes = _input.as_bytes();
-    let mut raw = Vec::with_capacity(bytes.len());
-    let mut width = None;
-    let mut current_width = 0usize;
-    let mut height = 0usize;
-
-    for &b in bytes {
-        match b {
-            b'@' => {
-                raw.push(true);
-                current_width += 1;
-            }
-            b'.' => {
-                raw.push(false);
-                current_width += 1;
+/// Rolls with fewer than this many neighbouring rolls can be reached.
+const ACCESSIBLE_BELOW: usize = 4;
+
+pub fn solve(ctx: &SolveContext<'_>) -> Result<DayResult> {
+    let board = ctx.time(Phase::Parse, || parse(ctx.input))?;
+
+    // removing rolls until none are accessible leaves the 4-core of the graph joining
+    // neighbouring rolls; the first round of peeling is part 1
+    let (peel, p1) = ctx.time(Phase::Part1, || {
+        let peel = KCore::new(
+            board.cells().len(),
+            rolls(&board),
+            ACCESSIBLE_BELOW,
+            |i| neighbours(&board, i),
+        );
+        let p1 = peel.first_round().len();
+        (peel, p1)
+    });
+    let peeling = ctx.time(Phase::Part2, || peel.peel());
+
+    ctx.visualize("day04.txt", || {
+        let trace = trace(&board, &peeling);
+        let mut out = String::new();
+        for (wave, frame) in trace.frames.iter().enumerate() {
+            match wave {
+                0 => out.push_str("start\n"),
+                _ => out.push_str(&format!(
+                    "wave {wave}: {} removed\n",
+                    trace.waves[wave - 1]
+                )),
             }
-            b'\n' => {
-                if current_width == 0 {
-                    continue;
-                }
-                if let Some(w) = width {
-                    debug_assert_eq!(w, current_width);
-                } else {
-                    width = Some(current_width);
-                }
-                height += 1;
-                current_width = 0;
-            }
-            _ => {}
-        }
-    }
-
-    if current_width > 0 {
-        if let Some(w) = width {
-            debug_assert_eq!(w, current_width);
-        } else {
-            width = Some(current_width);
+            out.push_str(&frame.to_string());
+            out.push('\n');
         }
-        height += 1;
-    }
-
-    let width = width.expect("lines must not be empty");
-    assert!(height > 0, "input must contain at least one row");
-    debug_assert_eq!(raw.len(), width * height);
-
-    let cells = raw.into_iter().map(Cell::new).collect();
-    let mut board = Grid::new(height, width, cells);
-    let rows = board.height();
-    let cols = board.width();
-    let rows_i = rows as isize;
-    let cols_i = cols as isize;
-    let mut queue = VecDeque::new();
-
-    for x in 0..rows {
-        for y in 0..cols {
-            if !board[(x, y)].alive {
-                continue;
-            }
-
-            let adjacent = alive_neighbor_count(&board, x, y, rows_i, cols_i);
-            {
-                let cell = board.get_mut(x, y);
-                cell.degree = adjacent;
-                if adjacent < 4 {
-                    cell.queued = true;
-                    queue.push_back((x, y));
-                }
-            }
-        }
-    }
-
-    let p1 = queue.len();
-    let mut removed = 0usize;
-
-    // Peel low-degree tiles using a queue so we only revisit cells whose degree drops.
-    while let Some((x, y)) = queue.pop_front() {
-        {
-            let cell = board.get_mut(x, y);
-            if !cell.alive {
-                cell.queued = false;
-                continue;
-            }
-            cell.alive = false;
-            cell.queued = false;
-        }
-        removed += 1;
-
-        for &(dx, dy) in &NEIGHBOR_OFFSETS {
-            let nx = x as isize + dx;
-            let ny = y as isize + dy;
-            if nx < 0 || nx >= rows_i || ny < 0 || ny >= cols_i {
-                continue;
-            }
-            let nx = nx as usize;
-            let ny = ny as usize;
-            let neighbor = board.get_mut(nx, ny);
-            if !neighbor.alive || neighbor.degree == 0 {
-                continue;
-            }
-            neighbor.degree -= 1;
-            if neighbor.degree == 3 && !neighbor.queued {
-                neighbor.queued = true;
-                queue.push_back((nx, ny));
-            }
-        }
-    }
+        Ok(out)
+    })?;
 
-    (p1, removed).into_result()
+    (p1, peeling.order.len()).into_result()
 }
 
-#[derive(Clone, Copy)]
-struct Cell {
-    alive: bool,
-    degree: u8,
-    queued: bool,
+/// Which cells hold rolls.
+fn parse(input: &str) -> Result<Grid<bool>, ParseError> {
+    Grid::try_parse(input, |b| match b {
+        b'@' => Ok(true),
+        b'.' => Ok(false),
+        _ => Err("'@' or '.'"),
+    })
 }
 
-impl Cell {
-    fn new(alive: bool) -> Self {
-        Self {
-            alive,
-            degree: 0,
-            queued: false,
-        }
-    }
+/// Rolls as indices into the board's cells, which are the nodes of the graph.
+fn rolls(board: &Grid<bool>) -> impl Iterator<Item = usize> + '_ {
+    let width = board.width();
+    board.find_all(&true).map(move |(row, col)| row * width + col)
 }
 
-#[derive(Clone)]
-struct Grid<T> {
-    data: Vec<T>,
-    height: usize,
-    width: usize,
+/// The cells around cell `i`, as indices into the board's cells.
+fn neighbours(board: &Grid<bool>, i: usize) -> impl Iterator<Item = usize> + '_ {
+    let width = board.width();
+    board
+        .neighbours8((i / width, i % width))
+        .map(move |(row, col)| row * width + col)
 }
 
-impl<T> Grid<T> {
-    fn new(height: usize, width: usize, data: Vec<T>) -> Self {
-        debug_assert_eq!(height * width, data.len());
-        Self {
-            data,
-            height,
-            width,
-        }
-    }
-
-    fn height(&self) -> usize {
-        self.height
-    }
-
-    fn width(&self) -> usize {
-        self.width
-    }
-
-    fn idx(&self, x: usize, y: usize) -> usize {
-        x * self.width + y
-    }
-
-    fn get(&self, x: usize, y: usize) -> &T {
-        let idx = self.idx(x, y);
-        unsafe { self.data.get_unchecked(idx) }
-    }
-
-    fn get_mut(&mut self, x: usize, y: usize) -> &mut T {
-        let idx = self.idx(x, y);
-        unsafe { self.data.get_unchecked_mut(idx) }
-    }
+/// How the board empties out, one wave of simultaneous removals at a time.
+pub struct Trace {
+    /// How many rolls each wave removed.
+    pub waves: Vec<usize>,
+    /// The board before any removals and then after each wave, drawn with `@` for
+    /// rolls, `x` for rolls the wave just removed and `.` for empty space.
+    pub frames: Vec<Grid<char>>,
 }
 
-impl<T> Index<(usize, usize)> for Grid<T> {
-    type Output = T;
-
-    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
-        self.get(x, y)
+/// Draws each wave of `peeling`, which must have been peeled from `board`.
+pub fn trace(board: &Grid<bool>, peeling: &Peeling) -> Trace {
+    let width = board.width();
+    let mut frame = board.map(|&roll| if roll { '@' } else { '.' });
+    let mut frames = vec![frame.clone()];
+    for wave in peeling.waves() {
+        frame.iter_mut().for_each(|(_, cell)| {
+            if *cell == 'x' {
+                *cell = '.';
+            }
+        });
+        for &i in wave {
+            frame[(i / width, i % width)] = 'x';
+        }
+        frames.push(frame.clone());
     }
-}
 
-impl<T> IndexMut<(usize, usize)> for Grid<T> {
-    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
-        self.get_mut(index.0, index.1)
+    Trace {
+        waves: peeling.rounds.clone(),
+        frames,
     }
 }
 
-fn alive_neighbor_count(board: &Grid<Cell>, x: usize, y: usize, rows: isize, cols: isize) -> u8 {
-    let xi = x as isize;
-    let yi = y as isize;
-    let mut adjacent = 0u8;
-    for &(dx, dy) in &NEIGHBOR_OFFSETS {
-        let nx = xi + dx;
-        let ny = yi + dy;
-        if nx < 0 || nx >= rows || ny < 0 || ny >= cols {
-            continue;
-        }
-        if board[(nx as usize, ny as usize)].alive {
-            adjacent += 1;
-        }
-    }
-    adjacent
-}
-
 #[cfg(test)]
 mod tests {
-    use crate::{days::day04::solve, IntoDayResult};
+    use crate::{
+        days::day04::{neighbours, parse, rolls, solve, trace, ACCESSIBLE_BELOW},
+        graph::k_core,
+        IntoDayResult, SolveContext,
+    };
 
     #[test]
     fn works_for_example() {
         const INPUT: &str = include_str!("../../test_input/day04.txt");
-        let solution = solve(INPUT).unwrap();
+        let solution = solve(&SolveContext::new(INPUT).with_test(true)).unwrap();
         assert_eq!((13, 43).into_day_result(), solution);
     }
 
     #[test]
-    fn works_for_input() {
-        const INPUT: &str =
-            include_str!(concat!(std::env!("AOC_CACHE"), "/2025_", "day04", ".txt"));
-        let solution = solve(INPUT).unwrap();
-        assert_eq!((1_602, 9_518).into_day_result(), solution);
+    fn traces_waves() {
+        const INPUT: &str = include_str!("../../test_input/day04.txt");
+        let board = parse(INPUT).unwrap();
+        let peeling = k_core(
+            board.cells().len(),
+            rolls(&board),
+            ACCESSIBLE_BELOW,
+            |i| neighbours(&board, i),
+        );
+        let trace = trace(&board, &peeling);
+        assert_eq!(vec![13, 12, 7, 5, 2, 1, 1, 1, 1], trace.waves);
+        assert_eq!(trace.waves.len() + 1, trace.frames.len());
+        assert_eq!(INPUT.replace("\r", ""), trace.frames[0].to_string());
+        assert_eq!(
+            13,
+            trace.frames[1].cells().iter().filter(|&&c| c == 'x').count()
+        );
+        let rolls = INPUT.bytes().filter(|&b| b == b'@').count();
+        let left = trace.frames.last().unwrap().find_all(&'@').count();
+        assert_eq!(rolls - 43, left);
+    }
+
+    #[test]
+    fn rejects_unknown_cells() {
+        let err = solve(&SolveContext::new("@.@\n.#.\n")).unwrap_err();
+        assert_eq!(
+            "line 2, column 2: expected '@' or '.', found '#'",
+            err.to_string()
+        );
     }
 }
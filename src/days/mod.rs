@@ -1,4 +1,6 @@
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
+crate::register_days! {
+    day01,
+    day02,
+    day03,
+    day04,
+}
@@ -0,0 +1,241 @@
+//! Core decomposition of undirected graphs.
+//!
+//! Nodes are indices below some `len`, and edges come from an adjacency callback that
+//! may also return indices which are not nodes (such as empty grid cells); those are
+//! ignored.
+use crate::search::{BitSet, BucketQueue};
+
+/// What peeling a graph down to its k-core removed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Peeling {
+    /// Every removed node, in the order they were removed.
+    pub order: Vec<usize>,
+    /// How many nodes were removed in each round. The first round is every node that
+    /// started with fewer than k neighbours, and each later round is the nodes that
+    /// fell below k through the removals of the round before.
+    pub rounds: Vec<usize>,
+    /// The nodes left, which make up the k-core.
+    pub core: Vec<usize>,
+}
+
+impl Peeling {
+    /// The nodes removed in each round.
+    pub fn waves(&self) -> impl Iterator<Item = &[usize]> {
+        self.rounds.iter().scan(0, |start, &count| {
+            let wave = &self.order[*start..*start + count];
+            *start += count;
+            Some(wave)
+        })
+    }
+}
+
+/// Neighbour counts for every node, counting only neighbours that are nodes.
+fn degrees<I>(
+    len: usize,
+    nodes: &[usize],
+    is_node: &BitSet,
+    adjacency: &impl Fn(usize) -> I,
+) -> Vec<usize>
+where
+    I: IntoIterator<Item = usize>,
+{
+    let mut degrees = vec![0; len];
+    for &node in nodes {
+        degrees[node] = adjacency(node)
+            .into_iter()
+            .filter(|&next| is_node.contains(next))
+            .count();
+    }
+    degrees
+}
+
+fn node_set(len: usize, nodes: impl IntoIterator<Item = usize>) -> (Vec<usize>, BitSet) {
+    let mut set = BitSet::new(len);
+    let nodes = nodes.into_iter().filter(|&node| set.insert(node)).collect();
+    (nodes, set)
+}
+
+/// Repeatedly removes every node with fewer than `k` neighbours until none are left.
+pub fn k_core<I>(
+    len: usize,
+    nodes: impl IntoIterator<Item = usize>,
+    k: usize,
+    adjacency: impl Fn(usize) -> I,
+) -> Peeling
+where
+    I: IntoIterator<Item = usize>,
+{
+    KCore::new(len, nodes, k, adjacency).peel()
+}
+
+/// A [`k_core`] peel that has found its first round but not removed anything yet, so
+/// the first round can be looked at (or timed) apart from the rest.
+pub struct KCore<F> {
+    nodes: Vec<usize>,
+    alive: BitSet,
+    degrees: Vec<usize>,
+    order: Vec<usize>,
+    k: usize,
+    adjacency: F,
+}
+
+impl<F, I> KCore<F>
+where
+    F: Fn(usize) -> I,
+    I: IntoIterator<Item = usize>,
+{
+    pub fn new(len: usize, nodes: impl IntoIterator<Item = usize>, k: usize, adjacency: F) -> Self {
+        let (nodes, alive) = node_set(len, nodes);
+        let degrees = degrees(len, &nodes, &alive, &adjacency);
+        let order = nodes
+            .iter()
+            .copied()
+            .filter(|&node| degrees[node] < k)
+            .collect();
+        Self {
+            nodes,
+            alive,
+            degrees,
+            order,
+            k,
+            adjacency,
+        }
+    }
+
+    /// Every node that starts with fewer than `k` neighbours.
+    pub fn first_round(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Removes rounds of nodes until none are left with fewer than `k` neighbours.
+    pub fn peel(self) -> Peeling {
+        let Self {
+            nodes,
+            mut alive,
+            mut degrees,
+            mut order,
+            k,
+            adjacency,
+        } = self;
+
+        let mut rounds = vec![];
+        let mut start = 0;
+        while start < order.len() {
+            let end = order.len();
+            rounds.push(end - start);
+            for i in start..end {
+                let node = order[i];
+                alive.remove(node);
+                for next in adjacency(node) {
+                    if !alive.contains(next) {
+                        continue;
+                    }
+                    degrees[next] -= 1;
+                    // only the removal that takes a node below k queues it, so nodes
+                    // are never queued twice
+                    if degrees[next] + 1 == k {
+                        order.push(next);
+                    }
+                }
+            }
+            start = end;
+        }
+
+        let core = nodes
+            .into_iter()
+            .filter(|&node| alive.contains(node))
+            .collect();
+        Peeling {
+            order,
+            rounds,
+            core,
+        }
+    }
+}
+
+/// The core number of every node: the largest k for which it is in the k-core. Indices
+/// that are not nodes get `None`.
+pub fn core_numbers<I>(
+    len: usize,
+    nodes: impl IntoIterator<Item = usize>,
+    adjacency: impl Fn(usize) -> I,
+) -> Vec<Option<usize>>
+where
+    I: IntoIterator<Item = usize>,
+{
+    let (nodes, is_node) = node_set(len, nodes);
+    let mut degrees = degrees(len, &nodes, &is_node, &adjacency);
+    let mut cores = vec![None; len];
+
+    let mut queue = BucketQueue::new();
+    for &node in &nodes {
+        queue.push(degrees[node], node);
+    }
+    // degrees are only ever lowered to the current core, which keeps the queue monotone;
+    // stale entries left behind by lowering are skipped
+    while let Some((core, node)) = queue.pop() {
+        if cores[node].is_some() || degrees[node] != core {
+            continue;
+        }
+        cores[node] = Some(core);
+        for next in adjacency(node) {
+            if !is_node.contains(next) || cores[next].is_some() || degrees[next] <= core {
+                continue;
+            }
+            degrees[next] -= 1;
+            queue.push(degrees[next], next);
+        }
+    }
+
+    cores
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{core_numbers, k_core, KCore, Peeling};
+
+    /// A triangle 0-1-2, with 3 hanging off 2, 4 off 3, and 5 / 6 not nodes.
+    fn adjacency(node: usize) -> Vec<usize> {
+        match node {
+            0 => vec![1, 2, 5],
+            1 => vec![0, 2],
+            2 => vec![0, 1, 3, 6],
+            3 => vec![2, 4],
+            4 => vec![3],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn peels_in_rounds() {
+        let peeling = k_core(7, 0..5, 2, adjacency);
+        assert_eq!(
+            Peeling {
+                order: vec![4, 3],
+                rounds: vec![1, 1],
+                core: vec![0, 1, 2],
+            },
+            peeling
+        );
+        assert_eq!(
+            vec![&[4][..], &[3][..]],
+            peeling.waves().collect::<Vec<_>>()
+        );
+
+        let peel = KCore::new(7, 0..5, 3, adjacency);
+        assert_eq!(&[0, 1, 3, 4], peel.first_round());
+        let peeling = peel.peel();
+        assert_eq!(vec![0, 1, 3, 4, 2], peeling.order);
+        assert_eq!(vec![4, 1], peeling.rounds);
+        assert!(peeling.core.is_empty());
+    }
+
+    #[test]
+    fn finds_core_numbers() {
+        assert_eq!(
+            vec![Some(2), Some(2), Some(2), Some(1), Some(1), None, None],
+            core_numbers(7, 0..5, adjacency)
+        );
+        assert_eq!(vec![Some(0), None], core_numbers(2, [0], |_| vec![1]));
+    }
+}
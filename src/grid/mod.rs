@@ -0,0 +1,513 @@
+//! Grids of cells: the dense [`Grid`], the unbounded [`SparseGrid`] and the toroidal
+//! [`WrappingGrid`], which share neighbour iteration and rendering through [`GridLike`].
+//!
+//! Positions are `(row, col)` pairs, with `(0, 0)` in the top left. Directions are
+//! `(d_row, d_col)` offsets, see [`ORTHOGONAL`] and [`ADJACENT`].
+use std::{
+    fmt::{Display, Formatter},
+    ops::{Index, IndexMut},
+};
+
+use crate::parse::{self, ParseError};
+
+mod sparse;
+mod wrapping;
+
+pub use sparse::{Point, SparseGrid};
+pub use wrapping::WrappingGrid;
+
+pub type Pos = (usize, usize);
+pub type Dir = (isize, isize);
+
+pub const UP: Dir = (-1, 0);
+pub const DOWN: Dir = (1, 0);
+pub const LEFT: Dir = (0, -1);
+pub const RIGHT: Dir = (0, 1);
+
+/// The four directions sharing an edge with a cell.
+pub const ORTHOGONAL: [Dir; 4] = [UP, RIGHT, DOWN, LEFT];
+
+/// All eight directions touching a cell, including diagonals.
+pub const ADJACENT: [Dir; 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// What the grid types have in common: looking cells up, stepping between positions and
+/// drawing themselves.
+pub trait GridLike {
+    type Pos: Copy;
+    type Cell;
+
+    fn get(&self, pos: Self::Pos) -> Option<&Self::Cell>;
+
+    /// The position one step from `pos` in `dir`, or `None` when that leaves the grid.
+    fn step(&self, pos: Self::Pos, dir: Dir) -> Option<Self::Pos>;
+
+    /// Every position to draw, a row at a time from the top.
+    fn position_rows(&self) -> impl Iterator<Item = impl Iterator<Item = Self::Pos>>;
+
+    /// The positions one step from `pos` in each of `dirs` that are still in the grid.
+    fn neighbours<'a>(
+        &'a self,
+        pos: Self::Pos,
+        dirs: &'a [Dir],
+    ) -> impl Iterator<Item = Self::Pos> + 'a {
+        dirs.iter().filter_map(move |&dir| self.step(pos, dir))
+    }
+
+    fn neighbours4(&self, pos: Self::Pos) -> impl Iterator<Item = Self::Pos> + '_ {
+        self.neighbours(pos, &ORTHOGONAL)
+    }
+
+    fn neighbours8(&self, pos: Self::Pos) -> impl Iterator<Item = Self::Pos> + '_ {
+        self.neighbours(pos, &ADJACENT)
+    }
+
+    /// Positions walking from `pos` (inclusive) in `dir` until leaving the grid. This
+    /// never ends on grids without edges.
+    fn ray(&self, pos: Self::Pos, dir: Dir) -> impl Iterator<Item = Self::Pos> + '_ {
+        std::iter::successors(Some(pos), move |&pos| self.step(pos, dir))
+    }
+
+    /// Draws one character per position, a row per line. Cells missing from sparse
+    /// grids are passed as `None`.
+    fn render(&self, f: impl Fn(Option<&Self::Cell>) -> char) -> String {
+        let mut out = String::new();
+        for row in self.position_rows() {
+            out.extend(row.map(|pos| f(self.get(pos))));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    height: usize,
+    width: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(height: usize, width: usize, data: Vec<T>) -> Self {
+        assert!(width > 0, "grids must be at least one cell wide");
+        assert_eq!(
+            height * width,
+            data.len(),
+            "grid data must be height * width"
+        );
+        Self {
+            data,
+            height,
+            width,
+        }
+    }
+
+    pub fn filled(height: usize, width: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(height, width, vec![value; height * width])
+    }
+
+    /// Parses one cell per byte of each line, failing on ragged or blank lines. The
+    /// last line may end with a line ending.
+    pub fn parse(input: &str, mut f: impl FnMut(u8) -> T) -> Result<Self, ParseError> {
+        Self::try_parse(input, |b| Ok(f(b)))
+    }
+
+    /// Like [`Grid::parse`], but `f` can reject a byte by saying what it expected
+    /// instead, such as `"'#' or '.'"`.
+    pub fn try_parse(
+        input: &str,
+        mut f: impl FnMut(u8) -> Result<T, &'static str>,
+    ) -> Result<Self, ParseError> {
+        let bytes = input.as_bytes();
+        let mut data = Vec::with_capacity(bytes.len());
+        let mut width = None;
+        let mut height = 0;
+        for line in parse::lines(bytes) {
+            // a blank line after the first is caught as a short row
+            if line.is_empty() && width.is_none() {
+                return Err(ParseError::at(bytes, line, "a row of cells"));
+            }
+            let width = *width.get_or_insert(line.len());
+            if line.len() > width {
+                return Err(ParseError::at(
+                    bytes,
+                    &line[width..],
+                    format!("the end of the row, as rows are {width} wide"),
+                ));
+            }
+            if line.len() < width {
+                return Err(ParseError::at(
+                    bytes,
+                    &line[line.len()..],
+                    format!("another cell, as rows are {width} wide"),
+                ));
+            }
+            for (i, &b) in line.iter().enumerate() {
+                data.push(f(b).map_err(|expected| ParseError::at(bytes, &line[i..], expected))?);
+            }
+            height += 1;
+        }
+
+        let Some(width) = width else {
+            return Err(ParseError::new(bytes, 0, "at least one row"));
+        };
+        Ok(Self::new(height, width, data))
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Every cell, row-major.
+    pub fn cells(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn in_bounds(&self, (row, col): Pos) -> bool {
+        row < self.height && col < self.width
+    }
+
+    fn idx(&self, (row, col): Pos) -> usize {
+        row * self.width + col
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.in_bounds(pos).then(|| &self.data[self.idx(pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if !self.in_bounds(pos) {
+            return None;
+        }
+        let idx = self.idx(pos);
+        Some(&mut self.data[idx])
+    }
+
+    /// # Safety
+    /// `pos` must be in bounds.
+    pub unsafe fn get_unchecked(&self, pos: Pos) -> &T {
+        debug_assert!(self.in_bounds(pos));
+        self.data.get_unchecked(self.idx(pos))
+    }
+
+    /// # Safety
+    /// `pos` must be in bounds.
+    pub unsafe fn get_unchecked_mut(&mut self, pos: Pos) -> &mut T {
+        debug_assert!(self.in_bounds(pos));
+        let idx = self.idx(pos);
+        self.data.get_unchecked_mut(idx)
+    }
+
+    /// Every position, row-major.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.data)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Pos, &mut T)> {
+        self.positions().zip(&mut self.data)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.data[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.data.chunks_exact(self.width)
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {col} out of bounds");
+        self.data[col..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// The down-right diagonal through `pos`, from its top left end.
+    pub fn diagonal(&self, (row, col): Pos) -> impl Iterator<Item = &T> {
+        let back = row.min(col);
+        let start = (row - back, col - back);
+        self.ray(start, (1, 1)).map(|pos| &self[pos])
+    }
+
+    /// The down-left diagonal through `pos`, from its top right end.
+    pub fn anti_diagonal(&self, (row, col): Pos) -> impl Iterator<Item = &T> {
+        let back = row.min(self.width - 1 - col);
+        let start = (row - back, col + back);
+        self.ray(start, (1, -1)).map(|pos| &self[pos])
+    }
+
+    /// The first position, row-major, whose cell matches `f`.
+    pub fn position(&self, f: impl Fn(&T) -> bool) -> Option<Pos> {
+        self.iter().find(|(_, cell)| f(cell)).map(|(pos, _)| pos)
+    }
+
+    pub fn find(&self, value: &T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        self.position(|cell| cell == value)
+    }
+
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Pos> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.height, self.width, self.data.iter().map(f).collect())
+    }
+
+    fn rebuild(&self, height: usize, width: usize, source: impl Fn(Pos) -> Pos) -> Self
+    where
+        T: Clone,
+    {
+        let data = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(|pos| self[source(pos)].clone())
+            .collect();
+        Self::new(height, width, data)
+    }
+
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rebuild(self.width, self.height, |(row, col)| (col, row))
+    }
+
+    /// Rotates a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rebuild(self.width, self.height, |(row, col)| {
+            (height - 1 - col, row)
+        })
+    }
+
+    /// Rotates a quarter turn anticlockwise.
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rebuild(self.width, self.height, |(row, col)| (col, width - 1 - row))
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rebuild(self.height, self.width, |(row, col)| (row, width - 1 - col))
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rebuild(self.height, self.width, |(row, col)| {
+            (height - 1 - row, col)
+        })
+    }
+}
+
+impl<T> GridLike for Grid<T> {
+    type Pos = Pos;
+    type Cell = T;
+
+    fn get(&self, pos: Pos) -> Option<&T> {
+        Grid::get(self, pos)
+    }
+
+    fn step(&self, (row, col): Pos, (d_row, d_col): Dir) -> Option<Pos> {
+        let pos = (
+            row.checked_add_signed(d_row)?,
+            col.checked_add_signed(d_col)?,
+        );
+        self.in_bounds(pos).then_some(pos)
+    }
+
+    fn position_rows(&self) -> impl Iterator<Item = impl Iterator<Item = Pos>> {
+        let width = self.width;
+        (0..self.height).map(move |row| (0..width).map(move |col| (row, col)))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} out of bounds"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::{Grid, GridLike, ADJACENT, DOWN, RIGHT};
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n", char::from).unwrap()
+    }
+
+    #[test]
+    fn parses_and_renders() {
+        let grid = grid();
+        assert_eq!((2, 3), (grid.height(), grid.width()));
+        assert_eq!('f', grid[(1, 2)]);
+        assert_eq!(None, grid.get((2, 0)));
+        assert_eq!("abc\ndef\n", grid.to_string());
+        assert_eq!(
+            "#.\n.#\n",
+            Grid::new(2, 2, vec![true, false, false, true]).render(|b| if b == Some(&true) {
+                '#'
+            } else {
+                '.'
+            })
+        );
+        let err = Grid::parse("ab\nc\n", char::from).unwrap_err();
+        assert_eq!((2, 2), (err.line, err.column));
+        let err = Grid::parse("ab\r\nabc\n", char::from).unwrap_err();
+        assert_eq!((2, 3), (err.line, err.column));
+        let err = Grid::parse("\n", char::from).unwrap_err();
+        assert_eq!(
+            (1, 1, "a row of cells"),
+            (err.line, err.column, &*err.expected)
+        );
+        assert!(Grid::parse("", char::from).is_err());
+        // a truncated grid is not a shorter valid one
+        let err = Grid::parse("ab\n\ncd\n", char::from).unwrap_err();
+        assert_eq!(
+            "line 2, column 1: expected another cell, as rows are 2 wide, found the end of the line",
+            err.to_string()
+        );
+        assert!(Grid::parse("ab\ncd\n\n", char::from).is_err());
+        let err = Grid::try_parse("#.\n.x\n", |b| match b {
+            b'#' => Ok(true),
+            b'.' => Ok(false),
+            _ => Err("'#' or '.'"),
+        })
+        .unwrap_err();
+        assert_eq!(
+            "line 2, column 2: expected '#' or '.', found 'x'",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one cell wide")]
+    fn rejects_zero_width() {
+        Grid::<char>::new(0, 0, vec![]);
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid = grid();
+        assert_eq!(
+            vec![(0, 1), (1, 0)],
+            grid.neighbours4((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(0, 0), (0, 2), (1, 0), (1, 1), (1, 2)],
+            grid.neighbours8((0, 1)).collect::<Vec<_>>()
+        );
+        assert_eq!(5, grid.neighbours((1, 1), &ADJACENT).count());
+        assert_eq!(None, grid.step((1, 2), RIGHT));
+        assert_eq!(Some((1, 2)), grid.step((0, 2), DOWN));
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let grid = grid();
+        assert_eq!(&['d', 'e', 'f'], grid.row(1));
+        assert_eq!("be", grid.column(1).collect::<String>());
+        assert_eq!(
+            vec!["ad", "be", "cf"],
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!("ae", grid.diagonal((1, 1)).collect::<String>());
+        assert_eq!("bf", grid.diagonal((0, 1)).collect::<String>());
+        assert_eq!("ce", grid.anti_diagonal((1, 1)).collect::<String>());
+        assert_eq!("bd", grid.anti_diagonal((1, 0)).collect::<String>());
+        assert_eq!(
+            vec![(0, 0), (0, 1), (0, 2)],
+            grid.ray((0, 0), RIGHT).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = grid();
+        assert_eq!("ad\nbe\ncf\n", grid.transpose().to_string());
+        assert_eq!("da\neb\nfc\n", grid.rotate_cw().to_string());
+        assert_eq!("cf\nbe\nad\n", grid.rotate_ccw().to_string());
+        assert_eq!("cba\nfed\n", grid.flip_horizontal().to_string());
+        assert_eq!("def\nabc\n", grid.flip_vertical().to_string());
+        assert_eq!(grid, grid.rotate_cw().rotate_cw().rotate_cw().rotate_cw());
+    }
+
+    #[test]
+    fn finds_values() {
+        let grid = Grid::parse("a.a\n.a.\n", char::from).unwrap();
+        assert_eq!(Some((0, 1)), grid.find(&'.'));
+        assert_eq!(None, grid.find(&'z'));
+        assert_eq!(
+            vec![(0, 0), (0, 2), (1, 1)],
+            grid.find_all(&'a').collect::<Vec<_>>()
+        );
+        let marked = grid.map(|&c| c == 'a');
+        assert_eq!(Some((0, 1)), marked.position(|&a| !a));
+    }
+}
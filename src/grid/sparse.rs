@@ -0,0 +1,167 @@
+use std::ops::{Index, RangeInclusive};
+
+use fxhash::FxHashMap;
+
+use crate::grid::{Dir, GridLike};
+
+/// A signed `(row, col)` position in a [`SparseGrid`].
+pub type Point = (isize, isize);
+
+/// An unbounded grid holding only the cells that have been set, for puzzles that grow
+/// in every direction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: FxHashMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: FxHashMap::default(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses one byte per cell of each line, keeping the cells `f` maps to `Some`. The
+    /// first byte of the first line is `(0, 0)`.
+    pub fn parse(input: &str, mut f: impl FnMut(u8) -> Option<T>) -> Self {
+        let cells = input
+            .lines()
+            .enumerate()
+            .flat_map(|(row, line)| {
+                line.bytes()
+                    .enumerate()
+                    .map(move |(col, b)| ((row as isize, col as isize), b))
+            })
+            .filter_map(|(point, b)| Some((point, f(b)?)))
+            .collect();
+        Self { cells }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    pub fn get_or_insert_with(&mut self, point: Point, f: impl FnOnce() -> T) -> &mut T {
+        self.cells.entry(point).or_insert_with(f)
+    }
+
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.cells.insert(point, value)
+    }
+
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        self.cells.remove(&point)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    /// The set cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&point, cell)| (point, cell))
+    }
+
+    /// The rows and columns spanned by the set cells, `None` when there are none.
+    pub fn bounds(&self) -> Option<(RangeInclusive<isize>, RangeInclusive<isize>)> {
+        let mut points = self.cells.keys();
+        let &(row, col) = points.next()?;
+        let (rows, cols) = points.fold(
+            ((row, row), (col, col)),
+            |((min_row, max_row), (min_col, max_col)), &(row, col)| {
+                (
+                    (min_row.min(row), max_row.max(row)),
+                    (min_col.min(col), max_col.max(col)),
+                )
+            },
+        );
+        Some((rows.0..=rows.1, cols.0..=cols.1))
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point:?} is not set"))
+    }
+}
+
+impl<T> GridLike for SparseGrid<T> {
+    type Pos = Point;
+    type Cell = T;
+
+    fn get(&self, point: Point) -> Option<&T> {
+        SparseGrid::get(self, point)
+    }
+
+    fn step(&self, (row, col): Point, (d_row, d_col): Dir) -> Option<Point> {
+        Some((row.checked_add(d_row)?, col.checked_add(d_col)?))
+    }
+
+    /// Covers the bounding box of the set cells.
+    fn position_rows(&self) -> impl Iterator<Item = impl Iterator<Item = Point>> {
+        self.bounds()
+            .into_iter()
+            .flat_map(|(rows, cols)| rows.map(move |row| cols.clone().map(move |col| (row, col))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::{GridLike, SparseGrid};
+
+    #[test]
+    fn grows_in_every_direction() {
+        let mut grid = SparseGrid::parse("#.\n.#\n", |b| (b == b'#').then_some(()));
+        assert_eq!(2, grid.len());
+        assert_eq!(Some((0..=1, 0..=1)), grid.bounds());
+
+        grid.insert((-2, 3), ());
+        assert_eq!(Some((-2..=1, 0..=3)), grid.bounds());
+        assert_eq!(
+            "...#\n....\n#...\n.#..\n",
+            grid.render(|cell| if cell.is_some() { '#' } else { '.' })
+        );
+        assert_eq!(8, grid.neighbours8((-2, 3)).count());
+
+        let mut set = grid
+            .neighbours8((0, 1))
+            .filter(|&point| grid.contains(point))
+            .collect::<Vec<_>>();
+        set.sort();
+        assert_eq!(vec![(0, 0), (1, 1)], set);
+    }
+
+    #[test]
+    fn renders_nothing_when_empty() {
+        assert_eq!("", SparseGrid::<u8>::new().render(|_| '#'));
+    }
+}
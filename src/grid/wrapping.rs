@@ -0,0 +1,130 @@
+use std::ops::{Index, IndexMut};
+
+use crate::grid::{Dir, Grid, GridLike, Point, Pos};
+
+/// A grid whose edges join up, so stepping off one side comes back on the other.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WrappingGrid<T> {
+    grid: Grid<T>,
+}
+
+impl<T> WrappingGrid<T> {
+    pub fn new(grid: Grid<T>) -> Self {
+        Self { grid }
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn grid_mut(&mut self) -> &mut Grid<T> {
+        &mut self.grid
+    }
+
+    pub fn into_inner(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// Maps any signed position onto the grid.
+    pub fn wrap(&self, (row, col): Point) -> Pos {
+        (
+            row.rem_euclid(self.grid.height() as isize) as usize,
+            col.rem_euclid(self.grid.width() as isize) as usize,
+        )
+    }
+
+    /// The cell at any signed position, wrapped onto the grid.
+    pub fn get_wrapped(&self, point: Point) -> &T {
+        &self.grid[self.wrap(point)]
+    }
+
+    pub fn get_wrapped_mut(&mut self, point: Point) -> &mut T {
+        let pos = self.wrap(point);
+        &mut self.grid[pos]
+    }
+}
+
+impl<T> Index<Pos> for WrappingGrid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        &self.grid[pos]
+    }
+}
+
+impl<T> IndexMut<Pos> for WrappingGrid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        &mut self.grid[pos]
+    }
+}
+
+impl<T> GridLike for WrappingGrid<T> {
+    type Pos = Pos;
+    type Cell = T;
+
+    fn get(&self, pos: Pos) -> Option<&T> {
+        self.grid.get(pos)
+    }
+
+    /// Always succeeds, wrapping around the edges.
+    fn step(&self, (row, col): Pos, (d_row, d_col): Dir) -> Option<Pos> {
+        Some(self.wrap((row as isize + d_row, col as isize + d_col)))
+    }
+
+    fn position_rows(&self) -> impl Iterator<Item = impl Iterator<Item = Pos>> {
+        self.grid.position_rows()
+    }
+
+    /// Each neighbour once, even on grids under 3 wide or tall, where several directions
+    /// wrap onto the same cell. On a grid 1 wide or tall a cell is its own neighbour.
+    fn neighbours<'a>(&'a self, pos: Pos, dirs: &'a [Dir]) -> impl Iterator<Item = Pos> + 'a {
+        let small = self.grid.height() < 3 || self.grid.width() < 3;
+        dirs.iter().enumerate().filter_map(move |(i, &dir)| {
+            let next = self.step(pos, dir)?;
+            let seen = small
+                && dirs[..i]
+                    .iter()
+                    .any(|&earlier| self.step(pos, earlier) == Some(next));
+            (!seen).then_some(next)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::{Grid, GridLike, WrappingGrid, LEFT, UP};
+
+    #[test]
+    fn wraps_around_edges() {
+        let grid = WrappingGrid::new(Grid::parse("abc\ndef\n", char::from).unwrap());
+        assert_eq!(Some((0, 2)), grid.step((0, 0), LEFT));
+        assert_eq!(Some((1, 1)), grid.step((0, 1), UP));
+        // only two rows, so up and down wrap onto the same cells
+        assert_eq!(5, grid.neighbours8((0, 0)).count());
+        assert_eq!('f', *grid.get_wrapped((-1, -1)));
+        assert_eq!('a', *grid.get_wrapped((4, 300)));
+        assert_eq!(
+            "cba",
+            grid.ray((0, 2), LEFT)
+                .take(3)
+                .map(|pos| grid[pos])
+                .collect::<String>()
+        );
+        assert_eq!("abc\ndef\n", grid.render(|c| *c.unwrap()));
+    }
+
+    #[test]
+    fn gives_each_neighbour_once_on_small_grids() {
+        let grid = WrappingGrid::new(Grid::parse("a\n", char::from).unwrap());
+        assert_eq!(vec![(0, 0)], grid.neighbours4((0, 0)).collect::<Vec<_>>());
+        assert_eq!(vec![(0, 0)], grid.neighbours8((0, 0)).collect::<Vec<_>>());
+
+        let grid = WrappingGrid::new(Grid::parse("ab\ncd\n", char::from).unwrap());
+        let mut neighbours = grid.neighbours4((0, 0)).collect::<Vec<_>>();
+        neighbours.sort();
+        assert_eq!(vec![(0, 1), (1, 0)], neighbours);
+        let mut neighbours = grid.neighbours8((0, 0)).collect::<Vec<_>>();
+        neighbours.sort();
+        assert_eq!(vec![(0, 1), (1, 0), (1, 1)], neighbours);
+    }
+}
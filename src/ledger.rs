@@ -0,0 +1,377 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use num::BigInt;
+
+use crate::{Answers, DayResult};
+
+/// What adventofcode.com said about a submitted answer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub answer: Answers,
+    pub verdict: Verdict,
+}
+
+/// Why a guess was refused before being recorded or submitted.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Rejection {
+    AlreadyTried(Verdict),
+    AlreadyAccepted,
+    KnownAnswer(String),
+    NotAbove(String),
+    NotBelow(String),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadyTried(verdict) => {
+                write!(f, "answer was already tried and was {verdict}")
+            }
+            Rejection::AlreadyAccepted => write!(f, "answer was already accepted"),
+            Rejection::KnownAnswer(answer) => write!(f, "the accepted answer is {answer}"),
+            Rejection::NotAbove(bound) => write!(f, "answer must be above {bound}"),
+            Rejection::NotBelow(bound) => write!(f, "answer must be below {bound}"),
+        }
+    }
+}
+
+impl std::error::Error for Rejection {}
+
+/// Every answer tried for a year, keyed by day and part, stored as
+/// `dayNN\tpart\tverdict\tanswer` lines in `{AOC_CACHE}/{year}_answers.tsv`. Tabs, line
+/// breaks and backslashes in answers are written as `\t`, `\n`, `\r` and `\\`.
+#[derive(Debug)]
+pub struct Ledger {
+    path: PathBuf,
+    entries: BTreeMap<(u32, u8), Vec<Entry>>,
+}
+
+impl Ledger {
+    pub fn path_for_year(year: i32) -> Result<PathBuf> {
+        let aoc_cache = std::env::var("AOC_CACHE").context("failed to read AOC_CACHE env var")?;
+        Ok(PathBuf::from(format!("{aoc_cache}/{year}_answers.tsv")))
+    }
+
+    pub fn for_year(year: i32) -> Result<Self> {
+        Self::load(Self::path_for_year(year)?)
+    }
+
+    /// Loads a ledger, treating a missing file as an empty ledger.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err).context("failed to read ledger"),
+        };
+
+        let mut entries: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for (i, line) in contents.lines().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+            let (key, entry) =
+                parse_line(line).with_context(|| format!("bad ledger line {}: {line:?}", i + 1))?;
+            entries.entry(key).or_default().push(entry);
+        }
+
+        Ok(Self { path, entries })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let mut contents = String::new();
+        for ((day, part), entries) in &self.entries {
+            for entry in entries {
+                contents.push_str(&format!(
+                    "day{day:0>2}\t{part}\t{verdict}\t{answer}\n",
+                    verdict = entry.verdict,
+                    answer = escape(&entry.answer.to_string())
+                ));
+            }
+        }
+
+        // write a sibling file and rename it over the ledger, so an interrupted save
+        // never leaves a truncated ledger behind
+        let file_name = self
+            .path
+            .file_name()
+            .with_context(|| format!("ledger path {:?} has no file name", self.path))?
+            .to_string_lossy();
+        let tmp = self.path.with_file_name(format!(".{file_name}.tmp"));
+        std::fs::write(&tmp, contents).context("failed to write ledger")?;
+        std::fs::rename(&tmp, &self.path).context("failed to replace ledger")
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = ((u32, u8), &Entry)> {
+        self.entries
+            .iter()
+            .flat_map(|(&key, entries)| entries.iter().map(move |entry| (key, entry)))
+    }
+
+    pub fn correct(&self, day: u32, part: u8) -> Option<&Answers> {
+        self.entries
+            .get(&(day, part))?
+            .iter()
+            .find(|entry| entry.verdict == Verdict::Correct)
+            .map(|entry| &entry.answer)
+    }
+
+    /// The accepted answers for a day, to compare a solver's output against.
+    pub fn expected(&self, day: u32) -> DayResult {
+        DayResult {
+            part1: self.correct(day, 1).cloned(),
+            part2: self.correct(day, 2).cloned(),
+        }
+    }
+
+    /// Refuses guesses that are already known to be wrong or fall outside the bounds
+    /// learnt from earlier too high / too low verdicts.
+    pub fn check(&self, day: u32, part: u8, answer: &Answers) -> Result<(), Rejection> {
+        let Some(entries) = self.entries.get(&(day, part)) else {
+            return Ok(());
+        };
+
+        if let Some(entry) = entries.iter().find(|entry| entry.answer.lenient_eq(answer)) {
+            return Err(match entry.verdict {
+                Verdict::Correct => Rejection::AlreadyAccepted,
+                verdict => Rejection::AlreadyTried(verdict),
+            });
+        }
+        if let Some(correct) = self.correct(day, part) {
+            return Err(Rejection::KnownAnswer(correct.to_string()));
+        }
+
+        let Some(value) = numeric(answer) else {
+            return Ok(());
+        };
+        let bound = |verdict| {
+            entries
+                .iter()
+                .filter(move |entry| entry.verdict == verdict)
+                .filter_map(|entry| numeric(&entry.answer))
+        };
+        if let Some(low) = bound(Verdict::TooLow).max() {
+            if value <= low {
+                return Err(Rejection::NotAbove(low.to_string()));
+            }
+        }
+        if let Some(high) = bound(Verdict::TooHigh).min() {
+            if value >= high {
+                return Err(Rejection::NotBelow(high.to_string()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks and then records a verdict, the ledger still needs saving afterwards. ASCII
+    /// art is recorded as the letters it spells.
+    pub fn record(&mut self, day: u32, part: u8, answer: Answers, verdict: Verdict) -> Result<()> {
+        let answer = answer
+            .normalised()
+            .with_context(|| format!("cannot record unreadable ascii art:\n{answer}"))?;
+        self.check(day, part, &answer)
+            .with_context(|| format!("refusing to record {answer} for day {day} part {part}"))?;
+        self.entries
+            .entry((day, part))
+            .or_default()
+            .push(Entry { answer, verdict });
+        Ok(())
+    }
+
+    /// Removes a recorded answer, returning whether one was found.
+    pub fn forget(&mut self, day: u32, part: u8, answer: &Answers) -> bool {
+        let Some(entries) = self.entries.get_mut(&(day, part)) else {
+            return false;
+        };
+        let before = entries.len();
+        entries.retain(|entry| !entry.answer.lenient_eq(answer));
+        before != entries.len()
+    }
+}
+
+/// Parses an answer as an integer where possible, falling back to a string.
+pub fn parse_answer(s: &str) -> Answers {
+    if let Ok(v) = s.parse::<i128>() {
+        return Answers::I128(v);
+    }
+    if let Ok(v) = s.parse::<u128>() {
+        return Answers::U128(v);
+    }
+    if let Ok(v) = s.parse::<BigInt>() {
+        return Answers::BigInt(v);
+    }
+    Answers::String(s.to_string())
+}
+
+fn numeric(answer: &Answers) -> Option<BigInt> {
+    answer.normalised()?.to_string().parse().ok()
+}
+
+fn escape(field: &str) -> String {
+    let mut escaped = String::with_capacity(field.len());
+    for c in field.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(field: &str) -> Result<String> {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        unescaped.push(match chars.next() {
+            Some('\\') => '\\',
+            Some('t') => '\t',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            other => return Err(anyhow!("bad escape {other:?} in answer")),
+        });
+    }
+    Ok(unescaped)
+}
+
+fn parse_line(line: &str) -> Result<((u32, u8), Entry)> {
+    let mut fields = line.splitn(4, '\t');
+    let mut field = |name| fields.next().ok_or_else(|| anyhow!("missing {name} field"));
+    let day = field("day")?
+        .strip_prefix("day")
+        .context("day field should look like dayNN")?
+        .parse()
+        .context("failed to parse day")?;
+    let part = field("part")?.parse().context("failed to parse part")?;
+    let verdict = field("verdict")?
+        .parse()
+        .context("failed to parse verdict")?;
+    let answer = parse_answer(&unescape(field("answer")?)?);
+
+    Ok(((day, part), Entry { answer, verdict }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ledger::{Ledger, Rejection, Verdict},
+        Answers,
+    };
+
+    fn ledger() -> Ledger {
+        let path = std::env::temp_dir().join(format!(
+            "aoc-ledger-{}-{:?}.tsv",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        Ledger::load(path).unwrap()
+    }
+
+    #[test]
+    fn refuses_known_wrong_and_out_of_bounds_guesses() {
+        let mut ledger = ledger();
+        ledger
+            .record(1, 1, 100_u64.into(), Verdict::TooHigh)
+            .unwrap();
+        ledger.record(1, 1, 10_u64.into(), Verdict::TooLow).unwrap();
+        ledger.record(1, 1, 50_u64.into(), Verdict::Wrong).unwrap();
+
+        assert_eq!(
+            Err(Rejection::AlreadyTried(Verdict::Wrong)),
+            ledger.check(1, 1, &50_u64.into())
+        );
+        assert_eq!(
+            Err(Rejection::NotBelow("100".to_string())),
+            ledger.check(1, 1, &150_u64.into())
+        );
+        assert_eq!(
+            Err(Rejection::NotAbove("10".to_string())),
+            ledger.check(1, 1, &5_u64.into())
+        );
+        assert_eq!(Ok(()), ledger.check(1, 1, &42_u64.into()));
+        assert_eq!(Ok(()), ledger.check(1, 2, &150_u64.into()));
+        assert!(ledger.record(1, 1, 150_u64.into(), Verdict::Wrong).is_err());
+
+        ledger
+            .record(1, 1, 42_u64.into(), Verdict::Correct)
+            .unwrap();
+        assert_eq!(
+            Err(Rejection::AlreadyAccepted),
+            ledger.check(1, 1, &42_u64.into())
+        );
+        assert_eq!(
+            Err(Rejection::KnownAnswer("42".to_string())),
+            ledger.check(1, 1, &43_u64.into())
+        );
+    }
+
+    #[test]
+    fn round_trips_through_file() {
+        let mut ledger = ledger();
+        ledger.record(3, 1, 17_u64.into(), Verdict::TooLow).unwrap();
+        ledger
+            .record(3, 1, 18_u64.into(), Verdict::Correct)
+            .unwrap();
+        ledger.record(3, 2, "ABC".into(), Verdict::Wrong).unwrap();
+        ledger
+            .record(3, 2, "a\tb\nc\\n".into(), Verdict::Wrong)
+            .unwrap();
+        ledger.save().unwrap();
+        let contents = std::fs::read_to_string(ledger.path()).unwrap();
+        assert!(contents.ends_with("day03\t2\twrong\ta\\tb\\nc\\\\n\n"));
+
+        let loaded = Ledger::load(ledger.path()).unwrap();
+        std::fs::remove_file(ledger.path()).unwrap();
+        assert_eq!(Some(&Answers::from(18_u64)), loaded.correct(3, 1));
+        assert_eq!(
+            vec![
+                ((3, 1), Verdict::TooLow),
+                ((3, 1), Verdict::Correct),
+                ((3, 2), Verdict::Wrong),
+                ((3, 2), Verdict::Wrong)
+            ],
+            loaded
+                .entries()
+                .map(|(key, entry)| (key, entry.verdict))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(&Answers::from("a\tb\nc\\n")),
+            loaded.entries().last().map(|(_, entry)| &entry.answer)
+        );
+        assert_eq!(
+            crate::DayResult {
+                part1: Some(18_u64.into()),
+                part2: None
+            },
+            loaded.expected(3)
+        );
+    }
+}
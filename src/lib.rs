@@ -1,17 +1,35 @@
 #![allow(dead_code)]
 use std::{
+    cell::RefCell,
     cmp::Reverse,
     fmt::{Debug, Display, Formatter},
+    hash::{Hash, Hasher},
+    ops::DerefMut,
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, Instant},
 };
 
 use anyhow::{Context, Result};
 use arrayvec::ArrayVec;
-use clap::Parser;
+use ascii_art::AsciiArt;
+use fxhash::FxHashMap;
+use num::{BigInt, BigUint};
+use serde::{Deserialize, Serialize};
 
+pub mod ascii_art;
 pub mod days;
+pub mod graph;
+pub mod grid;
+pub mod ledger;
+pub mod ocr;
+pub mod parse;
+pub mod search;
+pub mod subsequence;
 
 macro_rules! impl_answer_enum {
     ( $( ($variant:tt, $ty:ty) ),* ) => {
+        #[derive(Clone, Serialize, Deserialize)]
         pub enum Answers {
             $(
                 $variant($ty),
@@ -26,6 +44,25 @@ macro_rules! impl_answer_enum {
             }
         )*
 
+        impl Answers {
+            /// The name of the variant holding the answer, e.g. `U64`.
+            pub fn kind(&self) -> &'static str {
+                match self {
+                    $(
+                        Answers::$variant(_) => stringify!($variant),
+                    )*
+                }
+            }
+
+            fn key(&self) -> AnswerKey<'_> {
+                match self {
+                    $(
+                        Answers::$variant(v) => v.answer_key(),
+                    )*
+                }
+            }
+        }
+
         // assumes all types impl Display
         impl Display for Answers {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -46,23 +83,138 @@ macro_rules! impl_answer_enum {
                 }
             }
         }
+    }
+}
 
-        impl Eq for Answers {}
+/// What answers are compared, ordered and hashed by: integers by value whatever their
+/// width, strings as strings. Integers sort before strings and never equal them. ASCII
+/// art compares as the text it spells when that can be read, and otherwise only equals
+/// identical art. Integers only use the big variants when they do not fit in 128 bits,
+/// so the derived ordering is numeric.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum AnswerKey<'a> {
+    BigNegative(&'a BigInt),
+    Negative(i128),
+    NonNegative(u128),
+    BigPositive(&'a BigUint),
+    String(&'a str),
+    Art(&'a str),
+}
 
-        impl PartialEq for Answers {
-            fn eq(&self, other: &Self) -> bool {
-                let val_self = match self {
-                    $(
-                    Answers::$variant(v) => format!("{v}"),
-                    )*
-                };
-                let val_other = match other {
-                    $(
-                    Answers::$variant(v) => format!("{v}"),
-                    )*
-                };
-                val_self == val_other
+trait ToAnswerKey {
+    fn answer_key(&self) -> AnswerKey<'_>;
+}
+
+impl ToAnswerKey for String {
+    fn answer_key(&self) -> AnswerKey<'_> {
+        AnswerKey::String(self)
+    }
+}
+
+impl ToAnswerKey for BigInt {
+    fn answer_key(&self) -> AnswerKey<'_> {
+        if self.sign() == num::bigint::Sign::Minus {
+            match i128::try_from(self) {
+                Ok(v) => AnswerKey::Negative(v),
+                Err(_) => AnswerKey::BigNegative(self),
+            }
+        } else {
+            self.magnitude().answer_key()
+        }
+    }
+}
+
+impl ToAnswerKey for BigUint {
+    fn answer_key(&self) -> AnswerKey<'_> {
+        match u128::try_from(self) {
+            Ok(v) => AnswerKey::NonNegative(v),
+            Err(_) => AnswerKey::BigPositive(self),
+        }
+    }
+}
+
+impl ToAnswerKey for AsciiArt {
+    fn answer_key(&self) -> AnswerKey<'_> {
+        match self.text() {
+            Some(text) => AnswerKey::String(text),
+            None => AnswerKey::Art(self.art()),
+        }
+    }
+}
+
+macro_rules! impl_answer_key {
+    (unsigned: $( $ty:ty ),*; signed: $( $sty:ty ),*) => {
+        $(
+            impl ToAnswerKey for $ty {
+                fn answer_key(&self) -> AnswerKey<'_> {
+                    AnswerKey::NonNegative(*self as u128)
+                }
             }
+        )*
+        $(
+            impl ToAnswerKey for $sty {
+                fn answer_key(&self) -> AnswerKey<'_> {
+                    if *self < 0 {
+                        AnswerKey::Negative(*self as i128)
+                    } else {
+                        AnswerKey::NonNegative(*self as u128)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_key! {
+    unsigned: usize, u128, u64, u32, u16, u8;
+    signed: isize, i128, i64, i32, i16, i8
+}
+
+impl Eq for Answers {}
+
+impl PartialEq for Answers {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl PartialOrd for Answers {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Answers {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl Hash for Answers {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl Answers {
+    /// Compares answers by how they display, so `U8(3)` equals `String("3")`. Useful
+    /// when one side comes from untyped text such as an answers file or the ledger.
+    pub fn lenient_eq(&self, other: &Self) -> bool {
+        if self == other {
+            return true;
+        }
+        match (self.normalised(), other.normalised()) {
+            (Some(a), Some(b)) => a.to_string() == b.to_string(),
+            _ => false,
+        }
+    }
+
+    /// The answer as it would be typed into the site, where ASCII art becomes the
+    /// letters it spells. `None` if those letters could not be read.
+    pub fn normalised(&self) -> Option<Answers> {
+        match self {
+            Answers::AsciiArt(art) => art.text().map(Answers::from),
+            answer => Some(answer.clone()),
         }
     }
 }
@@ -80,7 +232,10 @@ impl_answer_enum! {
     (I64, i64),
     (I32, i32),
     (I16, i16),
-    (I8, i8)
+    (I8, i8),
+    (BigInt, BigInt),
+    (BigUint, BigUint),
+    (AsciiArt, AsciiArt)
 }
 
 impl From<&'_ str> for Answers {
@@ -144,33 +299,58 @@ where
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct DayResult {
     pub part1: Option<Answers>,
     pub part2: Option<Answers>,
 }
 
+impl DayResult {
+    /// [`Answers::lenient_eq`] for both parts.
+    pub fn lenient_eq(&self, other: &Self) -> bool {
+        let part = |a: &Option<Answers>, b: &Option<Answers>| match (a, b) {
+            (Some(a), Some(b)) => a.lenient_eq(b),
+            (a, b) => a.is_none() && b.is_none(),
+        };
+        part(&self.part1, &other.part1) && part(&self.part2, &other.part2)
+    }
+}
+
+/// Compares the wrapped value leniently, for `assert_eq!` in tests where one side is
+/// untyped: `assert_eq!(Lenient(expected), Lenient(solution))`.
+#[derive(Debug, Clone)]
+pub struct Lenient<T>(pub T);
+
+impl PartialEq for Lenient<Answers> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.lenient_eq(&other.0)
+    }
+}
+
+impl PartialEq for Lenient<DayResult> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.lenient_eq(&other.0)
+    }
+}
+
 impl Display for DayResult {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "DayResult {{")?;
-        writeln!(
-            f,
-            "\tpart 1: {p1}",
-            p1 = self
-                .part1
-                .as_ref()
-                .map(|v| v.to_string())
-                .unwrap_or("TBC".to_string())
-        )?;
-        writeln!(
-            f,
-            "\tpart 2: {p2}",
-            p2 = self
-                .part2
+        for (part, answer) in [(1, &self.part1), (2, &self.part2)] {
+            let answer = answer
                 .as_ref()
                 .map(|v| v.to_string())
-                .unwrap_or("TBC".to_string())
-        )?;
+                .unwrap_or("TBC".to_string());
+            if answer.contains('\n') {
+                // multi-line answers such as ascii art are printed as an indented block
+                writeln!(f, "\tpart {part}:")?;
+                for line in answer.lines() {
+                    writeln!(f, "\t\t{line}")?;
+                }
+            } else {
+                writeln!(f, "\tpart {part}: {answer}")?;
+            }
+        }
         writeln!(f, "}}")?;
         Ok(())
     }
@@ -192,7 +372,10 @@ impl<T, const N: usize> TryConvert for ArrayVec<T, N> {
     }
 }
 
-trait CollectN<T>
+/// Collects the `N` (or, for the `_k` methods, `k`) smallest or largest items of an
+/// iterator without sorting all of it, in O(n log N). Results come out sorted, smallest
+/// first for the smallest items and largest first for the largest.
+pub trait CollectN<T>
 where
     Self: Sized,
 {
@@ -200,14 +383,14 @@ where
     where
         T: Ord,
     {
-        self.collect_smallest().try_convert()
+        self.collect_largest().try_convert()
     }
 
     fn try_collect_smallest<const N: usize>(self) -> Result<[T; N], CollectError>
     where
         T: Ord,
     {
-        self.collect_largest().try_convert()
+        self.collect_smallest().try_convert()
     }
 
     fn try_collect_by_fn<const N: usize, F>(self, f: F) -> Result<[T; N], CollectError>
@@ -231,13 +414,33 @@ where
         self.collect_by_fn((|v| v) as for<'a> fn(&'a T) -> &'a T)
     }
 
+    /// The `N` items with the smallest keys.
     fn collect_by_fn<const N: usize, F>(self, f: F) -> ArrayVec<T, N>
     where
         F: for<'a> Callable<&'a T>;
+
+    fn collect_largest_k(self, k: usize) -> Vec<T>
+    where
+        T: Ord,
+    {
+        self.collect_by_fn_k(k, (|v| Reverse(v)) as for<'a> fn(&'a T) -> Reverse<&'a T>)
+    }
+
+    fn collect_smallest_k(self, k: usize) -> Vec<T>
+    where
+        T: Ord,
+    {
+        self.collect_by_fn_k(k, (|v| v) as for<'a> fn(&'a T) -> &'a T)
+    }
+
+    /// The `k` items with the smallest keys.
+    fn collect_by_fn_k<F>(self, k: usize, f: F) -> Vec<T>
+    where
+        F: for<'a> Callable<&'a T>;
 }
 
 #[derive(Debug)]
-struct CollectError {
+pub struct CollectError {
     expected: usize,
     actual: usize,
 }
@@ -264,33 +467,91 @@ where
         F: for<'a> Callable<&'a T>,
     {
         let mut res = ArrayVec::new();
+        select_smallest(self, N, &f, &mut res);
+        res
+    }
 
-        if N == 0 {
-            return res;
-        }
+    fn collect_by_fn_k<F>(self, k: usize, f: F) -> Vec<T>
+    where
+        F: for<'a> Callable<&'a T>,
+    {
+        // `k` can be far more than there are items, such as `usize::MAX` for all of them
+        let mut res = Vec::with_capacity(k.min(self.size_hint().0));
+        select_smallest(self, k, &f, &mut res);
+        res
+    }
+}
 
-        let comparer = |a: &_, b: &_| Ord::cmp(&f.call(a), &f.call(b));
+/// Storage for [`select_smallest`], which never pushes past its capacity.
+trait Buffer<T>: DerefMut<Target = [T]> {
+    fn push(&mut self, item: T);
+}
 
-        for (i, item) in self.enumerate() {
-            if i >= N {
-                let last = res
-                    .pop()
-                    .expect("there should always be a value as res cap is > 0");
-                let smallest = std::cmp::min_by(item, last, comparer);
+impl<T> Buffer<T> for Vec<T> {
+    fn push(&mut self, item: T) {
+        Vec::push(self, item);
+    }
+}
 
-                res.push(smallest);
-            } else {
-                res.push(item);
-            }
+impl<T, const N: usize> Buffer<T> for ArrayVec<T, N> {
+    fn push(&mut self, item: T) {
+        ArrayVec::push(self, item);
+    }
+}
+
+/// Keeps the `k` items with the smallest keys in `res` as a max-heap, so each item costs
+/// one comparison with the largest kept key and at most a log k sift, then sorts them.
+fn select_smallest<T, F>(items: impl Iterator<Item = T>, k: usize, f: &F, res: &mut impl Buffer<T>)
+where
+    F: for<'a> Callable<&'a T>,
+{
+    if k == 0 {
+        return;
+    }
 
-            res.sort_unstable_by(comparer);
+    let less = |a: &T, b: &T| f.call(a) < f.call(b);
+    for item in items {
+        if res.len() < k {
+            res.push(item);
+            let last = res.len() - 1;
+            sift_up(res, last, &less);
+        } else if less(&item, &res[0]) {
+            res[0] = item;
+            sift_down(res, 0, &less);
         }
+    }
 
-        res
+    res.sort_unstable_by(|a, b| f.call(a).cmp(&f.call(b)));
+}
+
+fn sift_up<T>(heap: &mut [T], mut i: usize, less: &impl Fn(&T, &T) -> bool) {
+    while i > 0 {
+        let parent = (i - 1) / 2;
+        if !less(&heap[parent], &heap[i]) {
+            break;
+        }
+        heap.swap(parent, i);
+        i = parent;
     }
 }
 
-trait Callable<T> {
+fn sift_down<T>(heap: &mut [T], mut i: usize, less: &impl Fn(&T, &T) -> bool) {
+    loop {
+        let mut largest = i;
+        for child in [2 * i + 1, 2 * i + 2] {
+            if child < heap.len() && less(&heap[largest], &heap[child]) {
+                largest = child;
+            }
+        }
+        if largest == i {
+            break;
+        }
+        heap.swap(i, largest);
+        i = largest;
+    }
+}
+
+pub trait Callable<T> {
     type Output: Ord;
 
     fn call(&self, arg: T) -> Self::Output;
@@ -318,58 +579,380 @@ pub fn get_input(day: &str, is_test: bool) -> Result<String> {
     std::fs::read_to_string(filepath).context("failed to read file")
 }
 
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-pub struct Args {
-    #[arg(short, long, default_value_t = false)]
-    pub test: bool,
-}
+/// Reads the known real input answers for a day from `answers/{day}.txt`, which holds a
+/// `part1: ...` and/or `part2: ...` line. Returns `None` if there is no answers file.
+pub fn get_answers(day: &str) -> Result<Option<DayResult>> {
+    let contents = match std::fs::read_to_string(format!("answers/{day}.txt")) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err).context("failed to read answers file"),
+    };
 
-#[macro_export]
-macro_rules! aoc_args_input_only {
-    ($solver:expr, $a:tt, $b:tt) => {
-        $solver($a)
+    let mut result = DayResult {
+        part1: None,
+        part2: None,
     };
+    for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+        let (part, answer) = line
+            .split_once(": ")
+            .with_context(|| format!("expected `partN: answer`, got {line:?}"))?;
+        let answer = Some(ledger::parse_answer(answer));
+        match part {
+            "part1" => result.part1 = answer,
+            "part2" => result.part2 = answer,
+            _ => anyhow::bail!("unknown part {part:?} in answers file"),
+        }
+    }
+
+    Ok(Some(result))
 }
 
-#[macro_export]
-macro_rules! aoc_args_both {
-    ($solver:expr, $a:tt, $b:tt) => {
-        $solver($a, $b)
-    };
+/// Everything a solver gets to see for a single run.
+///
+/// New per-run knobs belong here rather than in the solver signature, so every day
+/// can keep the same `solve(&SolveContext) -> Result<DayResult>` shape.
+#[derive(Clone, Debug, Default)]
+pub struct SolveContext<'a> {
+    pub input: &'a str,
+    pub is_test: bool,
+    pub params: Params,
+    /// Where solvers that can draw their progress should write it, e.g. via
+    /// `run --visualize`. Solvers without a visualisation ignore this.
+    pub visualize: Option<PathBuf>,
+    timings: RefCell<Vec<(Phase, Duration)>>,
 }
 
-#[macro_export]
-macro_rules! aoc_impl {
-    ($day:tt, $solver:tt) => {
-        use clap::Parser;
+impl<'a> SolveContext<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            is_test: false,
+            params: Params::default(),
+            visualize: None,
+            timings: RefCell::default(),
+        }
+    }
 
-        use $crate::days::$day::solve;
-        use $crate::get_input;
-        use $crate::Args;
+    /// Writes a visualisation named after the solver's day into the [`visualize`]
+    /// directory, doing nothing when visualising is off. `draw` is only called when the
+    /// output is wanted, so expensive traces cost nothing otherwise.
+    ///
+    /// [`visualize`]: SolveContext::visualize
+    pub fn visualize(&self, file_name: &str, draw: impl FnOnce() -> Result<String>) -> Result<()> {
+        let Some(dir) = &self.visualize else {
+            return Ok(());
+        };
+        std::fs::create_dir_all(dir).with_context(|| format!("failed to create {dir:?}"))?;
+        let path = dir.join(file_name);
+        std::fs::write(&path, draw()?).with_context(|| format!("failed to write {path:?}"))
+    }
 
-        fn main() -> anyhow::Result<()> {
-            let args = Args::parse();
-            let day = stringify!($day);
-            let is_test = std::env::var_os("TEST").is_some() || args.test;
-            let input = get_input(day, is_test)?;
-            let solution = $solver!(solve, (&input), is_test)?;
+    /// Runs `f` and records how long it took as `phase`. Solvers whose parsing and parts
+    /// are separable use this so the runner can report each phase on its own.
+    pub fn time<T>(&self, phase: Phase, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        let elapsed = start.elapsed();
+        self.timings.borrow_mut().push((phase, elapsed));
+        result
+    }
 
-            println!("{day}: {solution}");
+    /// Drains the phase timings recorded so far.
+    pub fn take_timings(&self) -> Vec<(Phase, Duration)> {
+        std::mem::take(&mut self.timings.borrow_mut())
+    }
+
+    pub fn with_test(mut self, is_test: bool) -> Self {
+        self.is_test = is_test;
+        self
+    }
 
-            Ok(())
+    pub fn with_param(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.insert(key, value);
+        self
+    }
+
+    pub fn with_params(mut self, params: Params) -> Self {
+        self.params = params;
+        self
+    }
+
+    pub fn with_visualize(mut self, dir: Option<PathBuf>) -> Self {
+        self.visualize = dir;
+        self
+    }
+}
+
+/// A separately timed part of a solver, see [`SolveContext::time`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+/// Free-form `key=value` parameters passed to a solver, e.g. via `run --params`.
+#[derive(Clone, Debug, Default)]
+pub struct Params(FxHashMap<String, String>);
+
+impl Params {
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.0.insert(key.into(), value.into());
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    pub fn get<T>(&self, key: &str) -> Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        self.get_str(key)
+            .map(|value| {
+                value
+                    .parse()
+                    .with_context(|| format!("failed to parse param {key}={value}"))
+            })
+            .transpose()
+    }
+}
+
+impl FromStr for Params {
+    type Err = anyhow::Error;
+
+    /// Parses a comma separated list of `key=value` pairs.
+    fn from_str(s: &str) -> Result<Self> {
+        let mut params = Params::default();
+        for pair in s.split(',').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair
+                .split_once('=')
+                .with_context(|| format!("expected key=value, got {pair:?}"))?;
+            params.insert(key, value);
         }
-    };
+        Ok(params)
+    }
+}
+
+/// A solver registered with [`register_days!`], see [`days::DAYS`].
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub number: u32,
+    pub name: &'static str,
+    pub solve: fn(&SolveContext<'_>) -> Result<DayResult>,
+}
+
+impl Debug for Day {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Day")
+            .field("number", &self.number)
+            .field("name", &self.name)
+            .finish()
+    }
 }
 
+/// Parses the number out of a `dayNN` module name at compile time.
+pub const fn day_number(name: &str) -> u32 {
+    let bytes = name.as_bytes();
+    let mut i = 0;
+    let mut number = 0;
+    while i < bytes.len() {
+        if bytes[i].is_ascii_digit() {
+            number = number * 10 + (bytes[i] - b'0') as u32;
+        }
+        i += 1;
+    }
+    number
+}
+
+/// Declares every day module and builds the `DAYS` registry from them.
+///
+/// This is the only place a new day has to be added. Attributes on a day, such as
+/// `#[cfg(feature = "slow")]`, apply to both its module and its registry entry.
 #[macro_export]
-macro_rules! aoc {
-    ($day:tt) => {
-        use $crate::aoc_args_input_only;
-        $crate::aoc_impl!($day, aoc_args_input_only);
-    };
-    ($day:tt, is_test) => {
-        use $crate::aoc_args_both;
-        $crate::aoc_impl!($day, aoc_args_both);
+macro_rules! register_days {
+    ( $( $(#[$meta:meta])* $day:ident ),* $(,)? ) => {
+        $(
+            $(#[$meta])*
+            pub mod $day;
+        )*
+
+        pub const DAYS: &[$crate::Day] = &[
+            $(
+                $(#[$meta])*
+                $crate::Day {
+                    number: $crate::day_number(stringify!($day)),
+                    name: stringify!($day),
+                    solve: $day::solve,
+                },
+            )*
+        ];
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use fxhash::FxHashSet;
+
+    use num::{BigInt, BigUint};
+
+    use proptest::prelude::*;
+
+    use crate::{ascii_art::AsciiArt, Answers, CollectN, IntoDayResult, Lenient};
+
+    mod registered {
+        crate::register_days! {
+            #[cfg(any())]
+            day99,
+        }
+    }
+
+    #[test]
+    fn registers_days_behind_attributes() {
+        assert!(registered::DAYS.is_empty());
+    }
+
+    #[test]
+    fn answers_compare_by_value() {
+        assert_eq!(Answers::U8(3), Answers::I64(3));
+        assert_eq!(Answers::U128(u128::MAX), Answers::U128(u128::MAX));
+        assert_ne!(Answers::U128(u128::MAX), Answers::I128(-1));
+        assert_ne!(Answers::U8(3), Answers::from("3"));
+        assert!(Answers::I8(-1) < Answers::U8(0));
+        assert!(Answers::I128(i128::MAX) < Answers::U128(u128::MAX));
+        assert!(Answers::U128(u128::MAX) < Answers::from("0"));
+
+        let set = [Answers::U8(3), Answers::I32(3), Answers::from("3")]
+            .into_iter()
+            .collect::<FxHashSet<_>>();
+        assert_eq!(2, set.len());
+        let set = [Answers::from("b"), Answers::I16(-4), Answers::Usize(2)]
+            .into_iter()
+            .collect::<BTreeSet<_>>();
+        assert_eq!(
+            vec![Answers::I16(-4), Answers::Usize(2), Answers::from("b")],
+            set.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn lenient_comparison_matches_display() {
+        assert!(Answers::U8(3).lenient_eq(&Answers::from("3")));
+        assert!(!Answers::U8(3).lenient_eq(&Answers::from("03")));
+        assert_eq!(
+            Lenient((3_u8, "4").into_day_result()),
+            Lenient(("3", 4_u64).into_day_result())
+        );
+        assert_ne!(
+            Lenient((3_u8,).into_day_result()),
+            Lenient(("3", 4_u64).into_day_result())
+        );
+    }
+
+    #[test]
+    fn answers_round_trip_through_serde() {
+        let result = (7_u64, "abc").into_day_result();
+        let json = serde_json::to_string(&result).unwrap();
+        assert_eq!(r#"{"part1":{"U64":7},"part2":{"String":"abc"}}"#, json);
+        let parsed: crate::DayResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(result, parsed);
+        assert_eq!("U64", parsed.part1.unwrap().kind());
+    }
+
+    #[test]
+    fn big_integers_compare_with_fixed_width_ones() {
+        assert_eq!(Answers::BigInt(BigInt::from(-5)), Answers::I8(-5));
+        assert_eq!(
+            Answers::BigUint(BigUint::from(u128::MAX)),
+            Answers::U128(u128::MAX)
+        );
+        let huge = BigUint::from(u128::MAX) * 10_u8;
+        assert_eq!(
+            Answers::BigUint(huge.clone()),
+            Answers::BigInt(BigInt::from(huge.clone()))
+        );
+        assert!(Answers::U128(u128::MAX) < Answers::BigUint(huge.clone()));
+        assert!(Answers::BigInt(-BigInt::from(huge)) < Answers::I128(i128::MIN));
+    }
+
+    #[test]
+    fn ascii_art_is_a_block() {
+        let art = AsciiArt::new("#.\n.#\n");
+        let result = (art.clone(), 3_u8).into_day_result();
+        assert_eq!(
+            "DayResult {\n\tpart 1:\n\t\t#.\n\t\t.#\n\tpart 2: 3\n}\n",
+            result.to_string()
+        );
+        assert_eq!(
+            Answers::from(art.clone()),
+            Answers::from(AsciiArt::new("#.  \n.#"))
+        );
+        assert_ne!(Answers::from(art.clone()), Answers::from("#.\n.#"));
+        assert_eq!(None, Answers::from(art).normalised());
+
+        let letters = AsciiArt::new("#..#.###\n#..#..#.\n####..#.\n#..#..#.\n#..#..#.\n#..#.###");
+        assert_eq!(Answers::from(letters.clone()), Answers::from("HI"));
+        assert_eq!(
+            Some(Answers::from("HI")),
+            Answers::from(letters).normalised()
+        );
+    }
+
+    #[test]
+    fn try_collects_the_named_end() {
+        let items = [5, 1, 4, 2, 3];
+        assert_eq!(
+            [5, 4],
+            items.into_iter().try_collect_largest::<2>().unwrap()
+        );
+        assert_eq!(
+            [1, 2],
+            items.into_iter().try_collect_smallest::<2>().unwrap()
+        );
+        let err = items.into_iter().try_collect_largest::<6>().unwrap_err();
+        assert_eq!(
+            "CollectError: expected 6 items, only consumed 5",
+            err.to_string()
+        );
+        assert!(items.into_iter().collect_smallest::<0>().is_empty());
+        assert_eq!(
+            vec![5, 4, 3, 2, 1],
+            items.into_iter().collect_largest_k(usize::MAX)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn collect_n_matches_a_full_sort(items in prop::collection::vec(-50i32..50, 0..200), k in 0usize..20) {
+            let mut sorted = items.clone();
+            sorted.sort();
+            let smallest = sorted.iter().copied().take(k).collect::<Vec<_>>();
+            let largest = sorted.iter().rev().copied().take(k).collect::<Vec<_>>();
+
+            prop_assert_eq!(&smallest, &items.iter().copied().collect_smallest_k(k));
+            prop_assert_eq!(&largest, &items.iter().copied().collect_largest_k(k));
+            prop_assert_eq!(
+                &sorted[..sorted.len().min(8)],
+                &items.iter().copied().collect_smallest::<8>()[..]
+            );
+            prop_assert_eq!(
+                &sorted.iter().rev().copied().take(8).collect::<Vec<_>>()[..],
+                &items.iter().copied().collect_largest::<8>()[..]
+            );
+        }
+
+        #[test]
+        fn collect_by_fn_keeps_the_smallest_keys(items in prop::collection::vec(0u32..1000, 0..200), k in 0usize..20) {
+            let key = |v: &u32| v % 7;
+            let mut keys = items.iter().map(key).collect::<Vec<_>>();
+            keys.sort();
+            keys.truncate(k);
+
+            let collected = items.into_iter().collect_by_fn_k(k, key);
+            prop_assert_eq!(keys, collected.iter().map(key).collect::<Vec<_>>());
+        }
+    }
+}
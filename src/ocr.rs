@@ -0,0 +1,224 @@
+//! Reads the letters drawn by puzzles that answer with a block of `#` / `.` glyphs.
+//!
+//! Both fonts advent of code uses are known: letters 6 cells tall (4 wide, with a blank
+//! column between them) and letters 10 cells tall (6 wide, with two blank columns).
+//! Letters are found by splitting on blank columns, so the spacing between them does
+//! not matter.
+use std::sync::LazyLock;
+
+const SMALL: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const LARGE: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// A glyph as the set cells of each column, one bit per row.
+type Columns = Vec<u16>;
+
+/// A font's height and its glyphs.
+type Font = (usize, Vec<(char, Columns)>);
+
+static FONTS: LazyLock<[Font; 2]> = LazyLock::new(|| {
+    let load = |font: &[(char, &str)]| {
+        font.iter()
+            .map(|&(letter, art)| {
+                let rows = art.lines().map(str::as_bytes).collect::<Vec<_>>();
+                let columns = (0..rows[0].len())
+                    .map(|x| column(rows.len(), |y| rows[y][x] == b'#'))
+                    .collect();
+                (letter, columns)
+            })
+            .collect()
+    };
+    [(6, load(SMALL)), (10, load(LARGE))]
+});
+
+fn column(height: usize, is_set: impl Fn(usize) -> bool) -> u16 {
+    (0..height).fold(0, |bits, y| bits | (u16::from(is_set(y)) << y))
+}
+
+/// Reads row-major cells, `width` to a row, returning `None` if the blank-trimmed
+/// drawing is not one of the known font heights or holds an unknown glyph.
+pub fn read(width: usize, cells: &[bool]) -> Option<String> {
+    if width == 0 || cells.is_empty() || !cells.len().is_multiple_of(width) {
+        return None;
+    }
+    let rows = cells.chunks(width).collect::<Vec<_>>();
+    let top = rows.iter().position(|row| row.contains(&true))?;
+    let bottom = rows.iter().rposition(|row| row.contains(&true))?;
+    let rows = &rows[top..=bottom];
+    let (_, font) = FONTS.iter().find(|(height, _)| *height == rows.len())?;
+
+    let columns = (0..width)
+        .map(|x| column(rows.len(), |y| rows[y][x]))
+        .collect::<Vec<_>>();
+    columns
+        .split(|&column| column == 0)
+        .filter(|glyph| !glyph.is_empty())
+        .map(|glyph| {
+            font.iter()
+                .find(|(_, columns)| columns == glyph)
+                .map(|&(letter, _)| letter)
+        })
+        .collect()
+}
+
+/// Reads letters drawn with `#` (or `█`) for set cells and anything else for unset ones.
+pub fn read_art(art: &str) -> Option<String> {
+    let rows = art
+        .lines()
+        .map(|line| {
+            line.chars()
+                .map(|c| c == '#' || c == '█')
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let width = rows.iter().map(Vec::len).max()?;
+    let cells = rows
+        .into_iter()
+        .flat_map(|mut row| {
+            row.resize(width, false);
+            row
+        })
+        .collect::<Vec<_>>();
+    read(width, &cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ocr::{read, read_art, LARGE, SMALL};
+
+    /// Lays glyphs out side by side with `gap` blank columns between them.
+    fn draw(font: &[(char, &str)], text: &str, gap: usize) -> String {
+        let glyphs = text
+            .chars()
+            .map(|c| font.iter().find(|&&(letter, _)| letter == c).unwrap().1)
+            .map(|art| art.lines().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        (0..glyphs[0].len())
+            .map(|y| {
+                glyphs
+                    .iter()
+                    .map(|glyph| glyph[y])
+                    .collect::<Vec<_>>()
+                    .join(&".".repeat(gap))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn reads_every_small_letter() {
+        let alphabet = SMALL.iter().map(|&(c, _)| c).collect::<String>();
+        assert_eq!(Some(alphabet.clone()), read_art(&draw(SMALL, &alphabet, 1)));
+        assert_eq!(Some(alphabet.clone()), read_art(&draw(SMALL, &alphabet, 3)));
+    }
+
+    #[test]
+    fn reads_every_large_letter() {
+        let alphabet = LARGE.iter().map(|&(c, _)| c).collect::<String>();
+        assert_eq!(Some(alphabet.clone()), read_art(&draw(LARGE, &alphabet, 2)));
+    }
+
+    #[test]
+    fn reads_cells_with_padding() {
+        let art = format!("\n{}\n\n", draw(SMALL, "HI", 1));
+        let rows = art.lines().collect::<Vec<_>>();
+        let width = 12;
+        let cells = rows
+            .iter()
+            .flat_map(|row| {
+                let mut row = row.bytes().map(|b| b == b'#').collect::<Vec<_>>();
+                row.insert(0, false);
+                row.resize(width, false);
+                row
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(Some("HI".to_string()), read(width, &cells));
+    }
+
+    #[test]
+    fn rejects_unknown_glyphs() {
+        assert_eq!(None, read_art("#.#\n.#.\n#.#"));
+        assert_eq!(None, read_art(&draw(SMALL, "AB", 1).replacen('#', ".", 1)));
+        assert_eq!(None, read_art(""));
+    }
+}
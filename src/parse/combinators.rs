@@ -0,0 +1,107 @@
+//! `nom` parsers for the formats inputs tend to use: numbers, `a-b` ranges, comma
+//! separated lists and one item per line.
+use crate::parse::{Integer, ParseError};
+use nom::{
+    character::complete::{char, line_ending, multispace0},
+    combinator::{all_consuming, cut},
+    error::{Error, ErrorKind},
+    multi::separated_list1,
+    sequence::{separated_pair, terminated},
+    IResult, Parser,
+};
+
+fn scanned<T>(input: &str, scan: fn(&[u8]) -> Option<(T, usize)>) -> IResult<&str, T> {
+    match scan(input.as_bytes()) {
+        // only ascii was consumed, so `len` is a char boundary
+        Some((value, len)) => Ok((&input[len..], value)),
+        None => Err(nom::Err::Error(Error::new(input, ErrorKind::Digit))),
+    }
+}
+
+/// A run of digits, failing if it overflows `T`.
+pub fn unsigned<T: Integer>(input: &str) -> IResult<&str, T> {
+    scanned(input, super::unsigned)
+}
+
+/// A run of digits with an optional leading `-` for signed types.
+pub fn signed<T: Integer>(input: &str) -> IResult<&str, T> {
+    scanned(input, super::signed)
+}
+
+/// An inclusive range written `a-b`.
+pub fn range<T: Integer>(input: &str) -> IResult<&str, (T, T)> {
+    separated_pair(unsigned, char('-'), unsigned).parse(input)
+}
+
+/// Items separated by commas, allowing whitespace (including line breaks) after each
+/// comma. A comma must be followed by an item, so errors point into a broken item
+/// rather than at the comma before it.
+pub fn comma_list<'a, O, P>(
+    item: P,
+) -> impl Parser<&'a str, Output = Vec<O>, Error = Error<&'a str>>
+where
+    P: Parser<&'a str, Output = O, Error = Error<&'a str>>,
+{
+    separated_list1(terminated(char(','), multispace0), cut(item))
+}
+
+/// One item per line, with `\n` or `\r\n` line endings.
+pub fn lines_of<'a, O, P>(item: P) -> impl Parser<&'a str, Output = Vec<O>, Error = Error<&'a str>>
+where
+    P: Parser<&'a str, Output = O, Error = Error<&'a str>>,
+{
+    separated_list1(line_ending, item)
+}
+
+/// Runs `parser` over the whole of `input`, allowing trailing whitespace, and points
+/// at where parsing stopped if it fails.
+pub fn finish<'a, O, P>(parser: P, input: &'a str) -> Result<O, ParseError>
+where
+    P: Parser<&'a str, Output = O, Error = Error<&'a str>>,
+{
+    match all_consuming(terminated(parser, multispace0)).parse(input) {
+        Ok((_, output)) => Ok(output),
+        Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
+            // the parser gives up at the line break before the bad byte rather than on it
+            let rest = err.input.trim_start();
+            let expected = match err.code {
+                ErrorKind::Eof => "the end of the input".to_string(),
+                ErrorKind::Digit => "a number".to_string(),
+                ErrorKind::Char => "a separator".to_string(),
+                code => code.description().to_lowercase(),
+            };
+            Err(ParseError::at(input.as_bytes(), rest.as_bytes(), expected))
+        }
+        Err(nom::Err::Incomplete(_)) => {
+            Err(ParseError::new(input.as_bytes(), input.len(), "more input"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::combinators::{comma_list, finish, lines_of, range, signed, unsigned};
+    use nom::{character::complete::char, sequence::preceded};
+
+    #[test]
+    fn parses_aoc_formats() {
+        assert_eq!(
+            vec![(11_u64, 22), (95, 115)],
+            finish(comma_list(range), "11-22,\n95-115\n").unwrap()
+        );
+        assert_eq!(
+            vec![-3_i32, 4],
+            finish(lines_of(preceded(char('x'), signed::<i32>)), "x-3\r\nx4").unwrap()
+        );
+        assert_eq!(Ok(("a", 12_u8)), unsigned::<u8>("12a"));
+        assert!(unsigned::<u8>("300").is_err());
+
+        let err = finish(lines_of(unsigned::<u32>), "1\n2\nx3\n").unwrap_err();
+        assert_eq!(
+            "line 3, column 1: expected the end of the input, found 'x'",
+            err.to_string()
+        );
+        let err = finish(comma_list(range::<u64>), "1-2,3-").unwrap_err();
+        assert_eq!((1, 7), (err.line, err.column));
+    }
+}
@@ -0,0 +1,98 @@
+use std::fmt::{Display, Formatter};
+
+/// Where and why an input failed to parse.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1 based line of the bad byte.
+    pub line: usize,
+    /// 1 based byte column of the bad byte, one past the end of the line if the line
+    /// ended too early.
+    pub column: usize,
+    /// The whole line the bad byte is on, without its line ending.
+    pub snippet: String,
+    /// What should have been there, such as `"'L' or 'R'"`.
+    pub expected: String,
+}
+
+impl ParseError {
+    /// An error at byte `offset` of `input`, which may be `input.len()` if the input
+    /// ended too early.
+    pub fn new(input: &[u8], offset: usize, expected: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let start = input[..offset]
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        let end = input[offset..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(input.len(), |i| offset + i);
+        let line = &input[start..end];
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+        Self {
+            line: input[..start].iter().filter(|&&b| b == b'\n').count() + 1,
+            column: offset - start + 1,
+            snippet: String::from_utf8_lossy(line).into_owned(),
+            expected: expected.into(),
+        }
+    }
+
+    /// An error at the start of `rest`, which must be a subslice of `input`. Handy when
+    /// scanning with a cursor that only keeps what is left to parse.
+    pub fn at(input: &[u8], rest: &[u8], expected: impl Into<String>) -> Self {
+        let offset = (rest.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        assert!(offset <= input.len(), "rest is not part of input");
+        Self::new(input, offset, expected)
+    }
+
+    /// The bad line with a caret under the bad byte, e.g.
+    ///
+    /// ```text
+    /// 3 | L68x
+    ///   |    ^ expected a digit or the end of the line
+    /// ```
+    pub fn render(&self) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let indent = self
+            .before()
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        format!(
+            "{number} | {}\n{gutter} | {indent}^ expected {}",
+            self.snippet, self.expected
+        )
+    }
+
+    /// The chars before the bad byte. `column` counts bytes, so this differs from the
+    /// first `column - 1` chars once the line has anything outside ASCII.
+    fn before(&self) -> impl Iterator<Item = char> + '_ {
+        self.snippet
+            .char_indices()
+            .take_while(|&(i, c)| i + c.len_utf8() < self.column)
+            .map(|(_, c)| c)
+    }
+
+    /// The char holding the bad byte.
+    fn found(&self) -> Option<char> {
+        let at = self.before().map(char::len_utf8).sum::<usize>();
+        self.snippet[at..].chars().next()
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, ",
+            self.line, self.column, self.expected
+        )?;
+        match self.found() {
+            Some(found) => write!(f, "found {found:?}"),
+            None => write!(f, "found the end of the line"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -0,0 +1,442 @@
+//! Allocation free helpers for the shapes puzzle inputs come in.
+//!
+//! The byte level functions here compile down to the same loops days would otherwise
+//! write by hand (see `benches/parse.rs`); [`combinators`] has `nom` parsers for inputs
+//! with more structure. Anything that fails to parse should say where with a
+//! [`ParseError`].
+pub mod combinators;
+mod error;
+
+pub use error::ParseError;
+
+/// Integers that can be read one decimal digit at a time.
+pub trait Integer: Copy {
+    const ZERO: Self;
+    const SIGNED: bool;
+    /// How many digits can always be read without overflowing.
+    const SAFE_DIGITS: usize;
+
+    /// `self * 10 + digit`, or `None` on overflow. Negative numbers accumulate
+    /// downwards, so `i8::MIN` can be read.
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+
+    /// [`Integer::push_digit`] for the first [`Integer::SAFE_DIGITS`] digits, which
+    /// skips the overflow checks.
+    fn push_safe_digit(self, digit: u8, negative: bool) -> Self;
+}
+
+macro_rules! impl_integer {
+    (unsigned: $( $ty:ty ),*; signed: $( $sty:ty ),*) => {
+        $(
+            impl Integer for $ty {
+                const ZERO: Self = 0;
+                const SIGNED: bool = false;
+                const SAFE_DIGITS: usize = <$ty>::MAX.ilog10() as usize;
+
+                #[inline(always)]
+                fn push_digit(self, digit: u8, _negative: bool) -> Option<Self> {
+                    self.checked_mul(10)?.checked_add(digit as Self)
+                }
+
+                #[inline(always)]
+                fn push_safe_digit(self, digit: u8, _negative: bool) -> Self {
+                    self.wrapping_mul(10).wrapping_add(digit as Self)
+                }
+            }
+        )*
+        $(
+            impl Integer for $sty {
+                const ZERO: Self = 0;
+                const SIGNED: bool = true;
+                const SAFE_DIGITS: usize = <$sty>::MAX.ilog10() as usize;
+
+                #[inline(always)]
+                fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                    let shifted = self.checked_mul(10)?;
+                    if negative {
+                        shifted.checked_sub(digit as Self)
+                    } else {
+                        shifted.checked_add(digit as Self)
+                    }
+                }
+
+                #[inline(always)]
+                fn push_safe_digit(self, digit: u8, negative: bool) -> Self {
+                    let shifted = self.wrapping_mul(10);
+                    if negative {
+                        shifted.wrapping_sub(digit as Self)
+                    } else {
+                        shifted.wrapping_add(digit as Self)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_integer! {
+    unsigned: u8, u16, u32, u64, u128, usize;
+    signed: i8, i16, i32, i64, i128, isize
+}
+
+/// Reads the digits at the start of `bytes`, returning the number and how many bytes
+/// it took. `None` if there are no digits or the number overflows `T`.
+#[inline]
+pub fn unsigned<T: Integer>(bytes: &[u8]) -> Option<(T, usize)> {
+    digits(bytes, false)
+}
+
+/// Like [`unsigned`], but allows a leading `-` for signed types.
+#[inline]
+pub fn signed<T: Integer>(bytes: &[u8]) -> Option<(T, usize)> {
+    match bytes.first() {
+        Some(b'-') if T::SIGNED => {
+            let (value, len) = digits(&bytes[1..], true)?;
+            Some((value, len + 1))
+        }
+        _ => digits(bytes, false),
+    }
+}
+
+#[inline(always)]
+fn digits<T: Integer>(bytes: &[u8], negative: bool) -> Option<(T, usize)> {
+    let mut value = T::ZERO;
+    let mut len = 0;
+    for &b in bytes {
+        if !b.is_ascii_digit() {
+            break;
+        }
+        value = if len < T::SAFE_DIGITS {
+            value.push_safe_digit(b - b'0', negative)
+        } else {
+            value.push_digit(b - b'0', negative)?
+        };
+        len += 1;
+    }
+    (len > 0).then_some((value, len))
+}
+
+/// Every integer in `bytes`, skipping whatever separates them. For signed types a `-`
+/// directly before a digit is a sign; for unsigned types it is just a separator, so
+/// `11-22` is two numbers. Numbers that overflow `T` end the iterator.
+pub fn integers<T: Integer>(bytes: &[u8]) -> Integers<'_, T> {
+    Integers {
+        bytes,
+        integer: std::marker::PhantomData,
+    }
+}
+
+pub struct Integers<'a, T> {
+    bytes: &'a [u8],
+    integer: std::marker::PhantomData<T>,
+}
+
+impl<T: Integer> Iterator for Integers<'_, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        let start = self
+            .bytes
+            .iter()
+            .position(|&b| b.is_ascii_digit() || (T::SIGNED && b == b'-'))?;
+        let rest = &self.bytes[start..];
+        match signed::<T>(rest) {
+            Some((value, len)) => {
+                self.bytes = &rest[len..];
+                Some(value)
+            }
+            // a `-` that is not followed by a digit
+            None if rest[0] == b'-' && rest.get(1).is_none_or(|b| !b.is_ascii_digit()) => {
+                self.bytes = &rest[1..];
+                self.next()
+            }
+            None => {
+                self.bytes = &[];
+                None
+            }
+        }
+    }
+}
+
+/// Splits on `\n`, dropping a `\r` before it and the empty line after a final newline.
+pub fn lines(bytes: &[u8]) -> Lines<'_> {
+    Lines { bytes }
+}
+
+pub struct Lines<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = &'a [u8];
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a [u8]> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+        let (line, rest) = match self.bytes.iter().position(|&b| b == b'\n') {
+            Some(end) => (&self.bytes[..end], &self.bytes[end + 1..]),
+            None => (self.bytes, &[][..]),
+        };
+        self.bytes = rest;
+        Some(line.strip_suffix(b"\r").unwrap_or(line))
+    }
+}
+
+/// Splits into blocks separated by blank lines, each without its trailing newline.
+pub fn paragraphs(bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut rest = bytes;
+    std::iter::from_fn(move || {
+        while let Some(stripped) = rest
+            .strip_prefix(b"\n")
+            .or_else(|| rest.strip_prefix(b"\r\n"))
+        {
+            rest = stripped;
+        }
+        if rest.is_empty() {
+            return None;
+        }
+
+        let mut end = rest.len();
+        let mut next = rest.len();
+        let mut i = 0;
+        while let Some(offset) = rest[i..].iter().position(|&b| b == b'\n') {
+            let newline = i + offset;
+            let after = &rest[newline + 1..];
+            let blank = after
+                .strip_prefix(b"\r\n")
+                .or_else(|| after.strip_prefix(b"\n"));
+            if blank.is_some() || after.is_empty() {
+                end = newline;
+                next = newline + 1;
+                break;
+            }
+            i = newline + 1;
+        }
+
+        let paragraph = &rest[..end];
+        rest = &rest[next..];
+        Some(paragraph.strip_suffix(b"\r").unwrap_or(paragraph))
+    })
+}
+
+/// How rows of a fixed width are laid out: `width` bytes of content followed by a
+/// `\n` or `\r\n` line ending.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RowLayout {
+    width: usize,
+    crlf: bool,
+}
+
+impl RowLayout {
+    /// Rows of `width` bytes ending in `\r\n` if `crlf`, or `\n` otherwise.
+    pub fn new(width: usize, crlf: bool) -> Self {
+        Self { width, crlf }
+    }
+
+    /// Takes the layout from the first line. `None` if the first line is empty.
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        let newline = bytes.iter().position(|&b| b == b'\n');
+        let (width, crlf) = match newline {
+            Some(0) => return None,
+            Some(i) if bytes[i - 1] == b'\r' => (i - 1, true),
+            Some(i) => (i, false),
+            None => (bytes.len(), false),
+        };
+        (width > 0).then_some(Self { width, crlf })
+    }
+
+    /// How many bytes of content each row has.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// How many bytes each row takes up, including its line ending.
+    pub fn stride(&self) -> usize {
+        self.width + self.ending().len()
+    }
+
+    fn ending(&self) -> &'static [u8] {
+        if self.crlf {
+            b"\r\n"
+        } else {
+            b"\n"
+        }
+    }
+
+    /// Checks that every row follows the layout and holds only cells accepted by
+    /// `is_cell`, returning how many rows there are. The last row may be missing its
+    /// line ending.
+    pub fn validate(
+        &self,
+        bytes: &[u8],
+        is_cell: impl Fn(u8) -> bool,
+        expected: &str,
+    ) -> Result<usize, ParseError> {
+        let ending = self.ending();
+        let mut rows = 0;
+        let mut rest = bytes;
+        while !rest.is_empty() {
+            let row = &rest[..self.width.min(rest.len())];
+            if let Some(bad) = row.iter().position(|&b| !is_cell(b)) {
+                return Err(ParseError::at(bytes, &row[bad..], expected));
+            }
+            if row.len() < self.width {
+                return Err(ParseError::at(
+                    bytes,
+                    &rest[row.len()..],
+                    format!("{expected}, as rows are {} wide", self.width),
+                ));
+            }
+            let after = &rest[self.width..];
+            rest = match after.strip_prefix(ending) {
+                Some(next) => next,
+                None if after.is_empty() => after,
+                None => {
+                    return Err(ParseError::at(
+                        bytes,
+                        after,
+                        format!("the end of the row, as rows are {} wide", self.width),
+                    ))
+                }
+            };
+            rows += 1;
+        }
+        Ok(rows)
+    }
+
+    /// The content of each row, assuming every row follows the layout. The last row
+    /// may be missing its line ending.
+    pub fn rows<'a>(&self, bytes: &'a [u8]) -> impl Iterator<Item = &'a [u8]> {
+        let width = self.width;
+        bytes
+            .chunks(self.stride())
+            .filter(move |chunk| chunk.len() >= width)
+            .map(move |chunk| &chunk[..width])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{integers, lines, paragraphs, signed, unsigned, ParseError, RowLayout};
+
+    #[test]
+    fn reads_integers() {
+        assert_eq!(Some((123_u32, 3)), unsigned(b"123abc"));
+        assert_eq!(None, unsigned::<u32>(b"abc"));
+        assert_eq!(None, unsigned::<u8>(b"256"));
+        assert_eq!(Some((-128_i8, 4)), signed(b"-128"));
+        assert_eq!(None, signed::<i8>(b"-129"));
+        assert_eq!(None, signed::<u8>(b"-1"));
+        assert_eq!(Some((7_i64, 1)), signed(b"7-"));
+    }
+
+    #[test]
+    fn finds_every_integer() {
+        let line = b"x=-3, y=10..-12 p 11-22";
+        assert_eq!(
+            vec![-3, 10, -12, 11, -22],
+            integers::<i32>(line).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![3, 10, 12, 11, 22],
+            integers::<u32>(line).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![5], integers::<i32>(b"- -- 5 -").collect::<Vec<_>>());
+        assert_eq!(vec![1], integers::<u8>(b"1 300 2").collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn splits_lines_and_paragraphs() {
+        assert_eq!(
+            vec![&b"a"[..], b"", b"b"],
+            lines(b"a\r\n\nb\n").collect::<Vec<_>>()
+        );
+        assert_eq!(0, lines(b"").count());
+        assert_eq!(
+            vec![&b"a\nb"[..], b"c", b"d"],
+            paragraphs(b"a\nb\n\nc\r\n\r\n\n\nd\n").collect::<Vec<_>>()
+        );
+        assert_eq!(0, paragraphs(b"\n\n").count());
+    }
+
+    #[test]
+    fn detects_fixed_width_rows() {
+        let layout = RowLayout::detect(b"123\n456\n789").unwrap();
+        assert_eq!(RowLayout::new(3, false), layout);
+        assert_eq!(
+            vec![&b"123"[..], b"456", b"789"],
+            layout.rows(b"123\n456\n789").collect::<Vec<_>>()
+        );
+
+        let crlf = b"12\r\n34\r\n";
+        let layout = RowLayout::detect(crlf).unwrap();
+        assert_eq!(RowLayout::new(2, true), layout);
+        assert_eq!((2, 4), (layout.width(), layout.stride()));
+        assert_eq!(
+            vec![&b"12"[..], b"34"],
+            layout.rows(crlf).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Ok(2),
+            layout.validate(crlf, |b| b.is_ascii_digit(), "a digit")
+        );
+        let err = layout
+            .validate(b"12\r\n3\r\n", |b| b.is_ascii_digit(), "a digit")
+            .unwrap_err();
+        assert_eq!((2, 2), (err.line, err.column));
+        let err = layout
+            .validate(b"12\r\n345\r\n", |b| b.is_ascii_digit(), "a digit")
+            .unwrap_err();
+        assert_eq!((2, 3), (err.line, err.column));
+        let err = layout
+            .validate(b"12\n34\n", |b| b.is_ascii_digit(), "a digit")
+            .unwrap_err();
+        assert_eq!((1, 3), (err.line, err.column));
+        let err = layout
+            .validate(b"12\r\n3x", |b| b.is_ascii_digit(), "a digit")
+            .unwrap_err();
+        assert_eq!(
+            "line 2, column 2: expected a digit, found 'x'",
+            err.to_string()
+        );
+        assert_eq!(None, RowLayout::detect(b"\n12"));
+        assert_eq!(None, RowLayout::detect(b""));
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_bad_byte() {
+        let input = b"L68\r\nR30\nL9x\n";
+        let err = ParseError::new(input, 11, "a digit");
+        assert_eq!((3, 3, "L9x"), (err.line, err.column, err.snippet.as_str()));
+        assert_eq!(
+            "line 3, column 3: expected a digit, found 'x'",
+            err.to_string()
+        );
+        assert_eq!("3 | L9x\n  |   ^ expected a digit", err.render());
+
+        let err = ParseError::at(input, &input[5..], "'L' or 'R'");
+        assert_eq!((2, 1, "R30"), (err.line, err.column, err.snippet.as_str()));
+
+        let err = ParseError::new(b"L1", 2, "a digit");
+        assert_eq!(
+            "line 1, column 3: expected a digit, found the end of the line",
+            err.to_string()
+        );
+
+        // columns count bytes, but the caret has to line up under chars
+        let err = ParseError::new("→ é9x".as_bytes(), 7, "a digit");
+        assert_eq!(
+            "line 1, column 8: expected a digit, found 'x'",
+            err.to_string()
+        );
+        assert_eq!("1 | → é9x\n  |     ^ expected a digit", err.render());
+        let err = ParseError::new("1é".as_bytes(), 1, "a digit");
+        assert_eq!(
+            "line 1, column 2: expected a digit, found 'é'",
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,606 @@
+//! Shortest path searches over any [`Graph`].
+//!
+//! Every search takes its starting nodes, a goal test and a [`Visited`] set. Use an
+//! [`FxHashSet`] for sparse or unbounded graphs and a [`DenseVisited`] bitset when nodes
+//! map onto a dense range of indices, such as positions in a [`Grid`].
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, VecDeque},
+    hash::Hash,
+    marker::PhantomData,
+};
+
+use fxhash::{FxHashMap, FxHashSet};
+
+use crate::grid::{Grid, Pos};
+
+pub type Cost = u64;
+
+/// Nodes and the cost of each edge leaving them.
+pub trait Graph {
+    type Node: Copy + Eq + Hash;
+
+    fn neighbours(&self, node: Self::Node) -> impl Iterator<Item = (Self::Node, Cost)>;
+}
+
+/// A [`Graph`] from a neighbour function, see [`from_fn`].
+pub struct FnGraph<N, F> {
+    f: F,
+    node: PhantomData<fn(N)>,
+}
+
+/// Makes a graph from a function giving each node's neighbours and edge costs.
+pub fn from_fn<N, I, F>(f: F) -> FnGraph<N, F>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = (N, Cost)>,
+    F: Fn(N) -> I,
+{
+    FnGraph {
+        f,
+        node: PhantomData,
+    }
+}
+
+impl<N, I, F> Graph for FnGraph<N, F>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = (N, Cost)>,
+    F: Fn(N) -> I,
+{
+    type Node = N;
+
+    fn neighbours(&self, node: N) -> impl Iterator<Item = (N, Cost)> {
+        (self.f)(node).into_iter()
+    }
+}
+
+/// The nodes a search has finished with.
+pub trait Visited<N> {
+    /// Marks `node` as visited, returning whether it was not already.
+    fn visit(&mut self, node: N) -> bool;
+
+    fn contains(&self, node: &N) -> bool;
+}
+
+impl<N: Eq + Hash> Visited<N> for FxHashSet<N> {
+    fn visit(&mut self, node: N) -> bool {
+        self.insert(node)
+    }
+
+    fn contains(&self, node: &N) -> bool {
+        FxHashSet::contains(self, node)
+    }
+}
+
+/// A fixed size set of `usize`s.
+#[derive(Clone, Debug, Default)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    /// Adds `i`, returning whether it was not already present.
+    pub fn insert(&mut self, i: usize) -> bool {
+        let (word, bit) = (i / 64, 1 << (i % 64));
+        let added = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        added
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    /// Removes `i`, returning whether it was present.
+    pub fn remove(&mut self, i: usize) -> bool {
+        let (word, bit) = (i / 64, 1 << (i % 64));
+        let removed = self.words[word] & bit != 0;
+        self.words[word] &= !bit;
+        removed
+    }
+
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+}
+
+/// A [`BitSet`] of nodes, each mapped to its bit by `index`.
+pub struct DenseVisited<F> {
+    bits: BitSet,
+    index: F,
+}
+
+impl<F> DenseVisited<F> {
+    /// A set for nodes whose `index` is below `len`.
+    pub fn new(len: usize, index: F) -> Self {
+        Self {
+            bits: BitSet::new(len),
+            index,
+        }
+    }
+}
+
+/// A visited set for positions in `grid`.
+pub fn grid_visited<T>(grid: &Grid<T>) -> DenseVisited<impl Fn(&Pos) -> usize> {
+    let width = grid.width();
+    DenseVisited::new(grid.height() * width, move |&(row, col): &Pos| {
+        row * width + col
+    })
+}
+
+impl<N, F: Fn(&N) -> usize> Visited<N> for DenseVisited<F> {
+    fn visit(&mut self, node: N) -> bool {
+        self.bits.insert((self.index)(&node))
+    }
+
+    fn contains(&self, node: &N) -> bool {
+        self.bits.contains((self.index)(node))
+    }
+}
+
+/// The cheapest route found to a goal, from a start node to the goal inclusive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<N> {
+    pub cost: Cost,
+    pub nodes: Vec<N>,
+}
+
+/// How each visited node was first reached, to rebuild paths from.
+struct Parents<N> {
+    parents: FxHashMap<N, N>,
+}
+
+impl<N: Copy + Eq + Hash> Parents<N> {
+    fn new() -> Self {
+        Self {
+            parents: FxHashMap::default(),
+        }
+    }
+
+    fn insert(&mut self, node: N, parent: Option<N>) {
+        if let Some(parent) = parent {
+            self.parents.insert(node, parent);
+        }
+    }
+
+    fn path(&self, cost: Cost, goal: N) -> Path<N> {
+        let mut nodes = vec![goal];
+        while let Some(&parent) = self.parents.get(nodes.last().expect("path is never empty")) {
+            nodes.push(parent);
+        }
+        nodes.reverse();
+        Path { cost, nodes }
+    }
+}
+
+/// Breadth first search, counting every edge as cost 1 whatever its real cost.
+pub fn bfs<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    mut is_goal: impl FnMut(&G::Node) -> bool,
+    mut visited: impl Visited<G::Node>,
+) -> Option<Path<G::Node>> {
+    let mut parents = Parents::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if visited.visit(start) {
+            queue.push_back((0, start));
+        }
+    }
+
+    while let Some((steps, node)) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(parents.path(steps, node));
+        }
+        for (next, _) in graph.neighbours(node) {
+            if visited.visit(next) {
+                parents.insert(next, Some(node));
+                queue.push_back((steps + 1, next));
+            }
+        }
+    }
+
+    None
+}
+
+/// The fewest steps from the starts to every reachable node.
+pub fn bfs_distances<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+) -> FxHashMap<G::Node, Cost> {
+    let mut distances = FxHashMap::default();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if distances.insert(start, 0).is_none() {
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        let steps = distances[&node];
+        for (next, _) in graph.neighbours(node) {
+            distances.entry(next).or_insert_with(|| {
+                queue.push_back(next);
+                steps + 1
+            });
+        }
+    }
+
+    distances
+}
+
+/// Cheapest path search. Edge costs must not be negative.
+pub fn dijkstra<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    is_goal: impl FnMut(&G::Node) -> bool,
+    visited: impl Visited<G::Node>,
+) -> Option<Path<G::Node>> {
+    best_first(graph, starts, is_goal, |_| 0, visited, Heap::default())
+}
+
+/// Cheapest path search guided by `heuristic`, which must be consistent: it never
+/// overestimates the remaining cost to a goal, and drops by at most an edge's cost
+/// across that edge.
+pub fn astar<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    is_goal: impl FnMut(&G::Node) -> bool,
+    heuristic: impl Fn(&G::Node) -> Cost,
+    visited: impl Visited<G::Node>,
+) -> Option<Path<G::Node>> {
+    best_first(graph, starts, is_goal, heuristic, visited, Heap::default())
+}
+
+/// [`dijkstra`] on a [`BucketQueue`], which is faster when edge costs are small
+/// integers.
+pub fn dijkstra_bucketed<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    is_goal: impl FnMut(&G::Node) -> bool,
+    visited: impl Visited<G::Node>,
+) -> Option<Path<G::Node>> {
+    best_first(
+        graph,
+        starts,
+        is_goal,
+        |_| 0,
+        visited,
+        BucketQueue::default(),
+    )
+}
+
+/// A node waiting to be visited, along with how it was reached.
+struct Queued<N> {
+    cost: Cost,
+    node: N,
+    parent: Option<N>,
+}
+
+trait Frontier<N> {
+    fn push(&mut self, priority: Cost, item: Queued<N>);
+
+    fn pop(&mut self) -> Option<Queued<N>>;
+}
+
+fn best_first<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    mut is_goal: impl FnMut(&G::Node) -> bool,
+    heuristic: impl Fn(&G::Node) -> Cost,
+    mut visited: impl Visited<G::Node>,
+    mut frontier: impl Frontier<G::Node>,
+) -> Option<Path<G::Node>> {
+    let mut parents = Parents::new();
+    for node in starts {
+        let item = Queued {
+            cost: 0,
+            node,
+            parent: None,
+        };
+        frontier.push(heuristic(&node), item);
+    }
+
+    // nodes can be queued more than once, only the cheapest is expanded
+    while let Some(Queued { cost, node, parent }) = frontier.pop() {
+        if !visited.visit(node) {
+            continue;
+        }
+        parents.insert(node, parent);
+        if is_goal(&node) {
+            return Some(parents.path(cost, node));
+        }
+
+        for (next, step) in graph.neighbours(node) {
+            if visited.contains(&next) {
+                continue;
+            }
+            let cost = cost + step;
+            let item = Queued {
+                cost,
+                node: next,
+                parent: Some(node),
+            };
+            frontier.push(cost + heuristic(&next), item);
+        }
+    }
+
+    None
+}
+
+struct HeapEntry<N> {
+    priority: Cost,
+    item: Queued<N>,
+}
+
+impl<N> PartialEq for HeapEntry<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N> Eq for HeapEntry<N> {}
+
+impl<N> PartialOrd for HeapEntry<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> Ord for HeapEntry<N> {
+    // reversed so the max-heap pops the lowest priority first
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+struct Heap<N>(BinaryHeap<HeapEntry<N>>);
+
+impl<N> Default for Heap<N> {
+    fn default() -> Self {
+        Self(BinaryHeap::new())
+    }
+}
+
+impl<N> Frontier<N> for Heap<N> {
+    fn push(&mut self, priority: Cost, item: Queued<N>) {
+        self.0.push(HeapEntry { priority, item });
+    }
+
+    fn pop(&mut self) -> Option<Queued<N>> {
+        self.0.pop().map(|entry| entry.item)
+    }
+}
+
+/// A monotone priority queue with a bucket per priority: nothing may be pushed with a
+/// lower priority than the last one popped.
+///
+/// Buckets are kept in a ring covering only the priorities that are queued, so for
+/// [`dijkstra_bucketed`] it holds at most one more bucket than the dearest edge, however
+/// long the paths get.
+#[derive(Clone, Debug)]
+pub struct BucketQueue<T> {
+    /// The bucket for priority `p` is at `p % buckets.len()`.
+    buckets: Vec<Vec<T>>,
+    current: usize,
+    len: usize,
+}
+
+impl<T> Default for BucketQueue<T> {
+    fn default() -> Self {
+        Self {
+            buckets: vec![],
+            current: 0,
+            len: 0,
+        }
+    }
+}
+
+impl<T> BucketQueue<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, priority: usize, item: T) {
+        assert!(
+            priority >= self.current,
+            "priority {priority} is below the current minimum {}",
+            self.current
+        );
+        let span = priority - self.current + 1;
+        if span > self.buckets.len() {
+            self.grow(span);
+        }
+        let ring = self.buckets.len();
+        self.buckets[priority % ring].push(item);
+        self.len += 1;
+    }
+
+    /// Moves every bucket into a ring of at least `span` buckets.
+    fn grow(&mut self, span: usize) {
+        let old = std::mem::take(&mut self.buckets);
+        let (old_ring, ring) = (old.len(), span.next_power_of_two());
+        self.buckets.resize_with(ring, Vec::new);
+        // queued priorities are all within `old_ring` of the current one
+        for (i, bucket) in old.into_iter().enumerate() {
+            let priority = self.current + (i + old_ring - self.current % old_ring) % old_ring;
+            self.buckets[priority % ring] = bucket;
+        }
+    }
+
+    /// Removes an item with the lowest priority.
+    pub fn pop(&mut self) -> Option<(usize, T)> {
+        if self.len == 0 {
+            return None;
+        }
+        let ring = self.buckets.len();
+        while self.buckets[self.current % ring].is_empty() {
+            self.current += 1;
+        }
+        self.len -= 1;
+        let item = self.buckets[self.current % ring].pop()?;
+        Some((self.current, item))
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<N> Frontier<N> for BucketQueue<Queued<N>> {
+    fn push(&mut self, priority: Cost, item: Queued<N>) {
+        let priority = usize::try_from(priority).expect("path cost does not fit in a usize");
+        BucketQueue::push(self, priority, item);
+    }
+
+    fn pop(&mut self) -> Option<Queued<N>> {
+        BucketQueue::pop(self).map(|(_, item)| item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fxhash::FxHashSet;
+
+    use crate::{
+        grid::{Grid, GridLike, Pos},
+        search::{
+            astar, bfs, bfs_distances, dijkstra, dijkstra_bucketed, from_fn, grid_visited,
+            BucketQueue, Path,
+        },
+    };
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#...
+.####.#.
+......#E
+";
+
+    fn maze() -> Grid<u8> {
+        Grid::parse(MAZE, |b| b).unwrap()
+    }
+
+    #[test]
+    fn bfs_finds_shortest_route_through_grid() {
+        let grid = maze();
+        let graph = from_fn(|pos: Pos| {
+            grid.neighbours4(pos)
+                .filter(|&next| grid[next] != b'#')
+                .map(|next| (next, 1))
+                .collect::<Vec<_>>()
+        });
+        let start = grid.find(&b'S').unwrap();
+        let end = grid.find(&b'E').unwrap();
+
+        let path = bfs(&graph, [start], |&pos| pos == end, grid_visited(&grid)).unwrap();
+        assert_eq!(15, path.cost);
+        assert_eq!(16, path.nodes.len());
+        assert_eq!(Some(&start), path.nodes.first());
+        assert_eq!(Some(&end), path.nodes.last());
+        assert!(path
+            .nodes
+            .windows(2)
+            .all(|pair| grid.neighbours4(pair[0]).any(|next| next == pair[1])));
+
+        let unreachable = bfs(&graph, [start], |_| false, FxHashSet::default());
+        assert_eq!(None, unreachable);
+        assert_eq!(Some(&15), bfs_distances(&graph, [start]).get(&end));
+    }
+
+    #[test]
+    fn weighted_searches_agree() {
+        // a line where the direct hop is dearer than walking
+        let graph = from_fn(|n: u32| {
+            let mut edges = vec![];
+            if n < 10 {
+                edges.push((n + 1, 2));
+            }
+            if n == 0 {
+                edges.push((10, 25));
+            }
+            edges
+        });
+        let expected = Path {
+            cost: 20,
+            nodes: (0..=10).collect(),
+        };
+        let goal = |&n: &u32| n == 10;
+
+        assert_eq!(
+            Some(&expected),
+            dijkstra(&graph, [0], goal, FxHashSet::default()).as_ref()
+        );
+        assert_eq!(
+            Some(&expected),
+            dijkstra_bucketed(&graph, [0], goal, FxHashSet::default()).as_ref()
+        );
+        assert_eq!(
+            Some(&expected),
+            astar(
+                &graph,
+                [0],
+                goal,
+                |&n| 10 - u64::from(n),
+                FxHashSet::default()
+            )
+            .as_ref()
+        );
+        // bfs ignores costs, so takes the single hop
+        assert_eq!(
+            Some(vec![0, 10]),
+            bfs(&graph, [0], goal, FxHashSet::default()).map(|path| path.nodes)
+        );
+    }
+
+    #[test]
+    fn bucket_queue_pops_in_priority_order() {
+        let mut queue = BucketQueue::new();
+        queue.push(3, 'c');
+        queue.push(1, 'a');
+        queue.push(2, 'b');
+        assert_eq!(Some((1, 'a')), queue.pop());
+        queue.push(1, 'd');
+        assert_eq!(3, queue.len());
+        assert_eq!(
+            vec![(1, 'd'), (2, 'b'), (3, 'c')],
+            std::iter::from_fn(|| queue.pop()).collect::<Vec<_>>()
+        );
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn bucket_queue_only_spans_queued_priorities() {
+        let mut queue = BucketQueue::new();
+        queue.push(0, 0);
+        for step in 1..1000 {
+            // wraps around the ring many times without growing it
+            queue.push(step * 1000, step);
+            queue.push(step * 1000 - 500, step);
+            assert_eq!(Some(((step - 1) * 1000, step - 1)), queue.pop());
+            assert_eq!(Some((step * 1000 - 500, step)), queue.pop());
+        }
+        assert_eq!(Some((999_000, 999)), queue.pop());
+        // at most 1500 apart were queued at once, against a last priority of 999,000
+        assert_eq!(2048, queue.buckets.len());
+    }
+}
@@ -0,0 +1,136 @@
+//! Lexicographically largest / smallest subsequences of a row of digits, such as
+//! day 3's best joltage from `k` batteries in a bank.
+use num::BigUint;
+
+/// Which end of the lexicographic order to pick from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Extreme {
+    Largest,
+    Smallest,
+}
+
+/// A subsequence of a row, kept in row order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Subsequence {
+    /// The picked bytes.
+    pub digits: Vec<u8>,
+    /// Where each picked byte is in the row, ascending.
+    pub indices: Vec<usize>,
+}
+
+impl Subsequence {
+    /// The picked ASCII digits read as a number, or `None` if it overflows a `u64`.
+    pub fn to_u64(&self) -> Option<u64> {
+        self.digit_values()
+            .try_fold(0_u64, |acc, d| acc.checked_mul(10)?.checked_add(d as u64))
+    }
+
+    /// The picked ASCII digits read as a number, or `None` if it overflows a `u128`.
+    pub fn to_u128(&self) -> Option<u128> {
+        self.digit_values()
+            .try_fold(0_u128, |acc, d| acc.checked_mul(10)?.checked_add(d as u128))
+    }
+
+    /// The picked ASCII digits read as a number of any size.
+    pub fn to_big_uint(&self) -> BigUint {
+        self.digit_values()
+            .fold(BigUint::ZERO, |acc, d| acc * 10_u8 + d)
+    }
+
+    fn digit_values(&self) -> impl Iterator<Item = u8> + '_ {
+        self.digits.iter().map(|&b| {
+            debug_assert!(b.is_ascii_digit(), "{b} is not a digit");
+            b - b'0'
+        })
+    }
+}
+
+/// The lexicographically largest or smallest length `k` subsequence of `row`, or `None`
+/// if the row is shorter than `k`. Of the subsequences with the same bytes, the one
+/// that picks each byte as early as possible is returned.
+pub fn best_subsequence(row: &[u8], k: usize, extreme: Extreme) -> Option<Subsequence> {
+    let mut to_remove = row.len().checked_sub(k)?;
+
+    // a byte is dropped when a better one comes after it and there are still bytes to
+    // spare; ties are kept, so equal bytes are picked from the left
+    let mut indices = Vec::with_capacity(row.len());
+    for (i, &b) in row.iter().enumerate() {
+        while to_remove > 0 {
+            let Some(&top) = indices.last() else {
+                break;
+            };
+            let better = match extreme {
+                Extreme::Largest => b > row[top],
+                Extreme::Smallest => b < row[top],
+            };
+            if !better {
+                break;
+            }
+            indices.pop();
+            to_remove -= 1;
+        }
+        indices.push(i);
+    }
+    indices.truncate(k);
+
+    Some(Subsequence {
+        digits: indices.iter().map(|&i| row[i]).collect(),
+        indices,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::subsequence::{best_subsequence, Extreme, Subsequence};
+    use itertools::Itertools;
+    use num::BigUint;
+    use proptest::prelude::*;
+    use std::cmp::Reverse;
+
+    #[test]
+    fn picks_digits_and_indices() {
+        assert_eq!(
+            Some(Subsequence {
+                digits: b"92".to_vec(),
+                indices: vec![1, 3],
+            }),
+            best_subsequence(b"1912", 2, Extreme::Largest)
+        );
+        assert_eq!(
+            Some(vec![0, 2]),
+            best_subsequence(b"1912", 2, Extreme::Smallest).map(|s| s.indices)
+        );
+        assert_eq!(None, best_subsequence(b"12", 3, Extreme::Largest));
+        assert_eq!(
+            Some(0),
+            best_subsequence(b"12", 0, Extreme::Largest).and_then(|s| s.to_u64())
+        );
+
+        let long = best_subsequence(&[b'9'; 40], 39, Extreme::Largest).unwrap();
+        assert_eq!(None, long.to_u64());
+        assert_eq!(None, long.to_u128());
+        assert_eq!(BigUint::from(10_u8).pow(39) - 1_u8, long.to_big_uint());
+        let short = best_subsequence(b"98765432101", 11, Extreme::Largest).unwrap();
+        assert_eq!(Some(98_765_432_101), short.to_u64());
+        assert_eq!(Some(98_765_432_101), short.to_u128());
+    }
+
+    proptest! {
+        #[test]
+        fn matches_every_combination(row in "[0-9]{0,10}", k in 0_usize..12) {
+            let row = row.as_bytes();
+            for extreme in [Extreme::Largest, Extreme::Smallest] {
+                // combinations come in lexicographic order of indices, so the first
+                // best one is also the one picking earliest
+                let combinations = (0..row.len()).combinations(k);
+                let digits = |indices: &Vec<usize>| indices.iter().map(|&i| row[i]).collect::<Vec<_>>();
+                let expected = match extreme {
+                    Extreme::Largest => combinations.min_by_key(|indices| Reverse(digits(indices))),
+                    Extreme::Smallest => combinations.min_by_key(digits),
+                };
+                let picked = best_subsequence(row, k, extreme);
+                prop_assert_eq!(expected, picked.map(|s| s.indices), "{:?}", extreme);
+            }
+        }
+    }
+}
@@ -0,0 +1,261 @@
+//! Runs the `aoc` scaffolding binary against throwaway projects in the temp dir.
+use std::{
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+#[path = "../src/bin/aoc/test_server.rs"]
+mod test_server;
+
+const MOD_FILE: &str = "crate::register_days! {\n    day01,\n    day03,\n}\n";
+
+struct Project {
+    root: PathBuf,
+    cache: PathBuf,
+}
+
+impl Project {
+    fn new(name: &str) -> Self {
+        let root = std::env::temp_dir().join(format!(
+            "advent-of-code-scaffold-{}-{name}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src/days")).unwrap();
+        std::fs::create_dir_all(root.join("test_input")).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"advent-of-code-2025\"\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("src/days/mod.rs"), MOD_FILE).unwrap();
+
+        let cache = root.join("cache");
+        Self { root, cache }
+    }
+
+    fn aoc(&self, args: &[&str], base_url: Option<&str>) -> Output {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_aoc"));
+        command
+            .args(args)
+            .arg("--root")
+            .arg(&self.root)
+            .args(["--year", "2025"])
+            .env("AOC_CACHE", &self.cache)
+            .env("AOC_SESSION", "secret")
+            .env_remove("AOC_CONFIG")
+            .env_remove("AOC_CONTACT")
+            .env_remove("AOC_OFFLINE");
+        match base_url {
+            Some(base_url) => command.env("AOC_BASE_URL", base_url),
+            None => command.env("AOC_BASE_URL", "http://127.0.0.1:9"),
+        };
+        command.output().unwrap()
+    }
+
+    fn cache_input(&self, day: &str, input: &str) {
+        std::fs::create_dir_all(&self.cache).unwrap();
+        std::fs::write(self.cache.join(format!("2025_{day}.txt")), input).unwrap();
+    }
+
+    fn read(&self, path: &str) -> String {
+        std::fs::read_to_string(self.root.join(path)).unwrap()
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.root.join(path).exists()
+    }
+}
+
+impl Drop for Project {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+}
+
+fn assert_success(output: &Output) {
+    assert!(
+        output.status.success(),
+        "aoc failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// A cheap sanity check on generated source. Balanced braces do not show that it
+/// compiles, only that the template was not cut short or mangled.
+fn assert_braces_balanced(path: &Path, source: &str) {
+    let mut depth = 0i32;
+    for c in source.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            _ => {}
+        }
+        assert!(depth >= 0, "{path:?} closes a brace it never opened");
+    }
+    assert_eq!(0, depth, "{path:?} has unbalanced braces");
+}
+
+#[test]
+fn generates_a_day_and_downloads_its_input() {
+    let project = Project::new("generate");
+    let (base_url, server) = test_server::serve_once(200, "1 2 3\n".to_string());
+
+    let output = project.aoc(&["2"], Some(&base_url));
+    assert_success(&output);
+
+    let request = server.join().unwrap();
+    assert_eq!("GET /2025/day/2/input HTTP/1.1", request.request_line);
+    assert_eq!("session=secret", request.cookie);
+    assert_eq!(
+        "https://github.com/jchevertonwynne/advent-of-code-2025",
+        request.user_agent
+    );
+    assert_eq!("", request.body);
+
+    let solver = project.read("src/days/day02.rs");
+    assert_braces_balanced(Path::new("src/days/day02.rs"), &solver);
+    assert!(solver.contains("pub fn solve(_ctx: &SolveContext<'_>) -> Result<DayResult>"));
+    assert!(solver.contains("use crate::{days::day02::solve, IntoDayResult, SolveContext};"));
+    assert!(solver.contains(r#"include_str!("../../test_input/day02.txt")"#));
+
+    assert_eq!("", project.read("test_input/day02.txt"));
+    assert_eq!(
+        "crate::register_days! {\n    day01,\n    day02,\n    day03,\n}\n",
+        project.read("src/days/mod.rs")
+    );
+    assert_eq!(
+        "1 2 3\n",
+        std::fs::read_to_string(project.cache.join("2025_day02.txt")).unwrap()
+    );
+}
+
+#[test]
+fn keeps_mod_file_sorted() {
+    let project = Project::new("sorted");
+    for day in ["day05", "day04", "day02"] {
+        project.cache_input(day, "");
+    }
+
+    for day in ["5", "4", "2"] {
+        assert_success(&project.aoc(&[day, "--offline"], None));
+    }
+
+    assert_eq!(
+        "crate::register_days! {\n    day01,\n    day02,\n    day03,\n    day04,\n    day05,\n}\n",
+        project.read("src/days/mod.rs")
+    );
+}
+
+#[test]
+fn does_not_overwrite_without_flag() {
+    let project = Project::new("overwrite");
+    project.cache_input("day04", "input");
+    assert_success(&project.aoc(&["4", "--offline"], None));
+
+    std::fs::write(project.root.join("src/days/day04.rs"), "// my solution").unwrap();
+    std::fs::write(project.root.join("test_input/day04.txt"), "example").unwrap();
+
+    let output = project.aoc(&["4", "--offline"], None);
+    assert!(!output.status.success());
+    assert_eq!("// my solution", project.read("src/days/day04.rs"));
+    assert_eq!("example", project.read("test_input/day04.txt"));
+
+    assert_success(&project.aoc(&["4", "--offline", "--overwrite"], None));
+    assert!(project
+        .read("src/days/day04.rs")
+        .contains("pub fn solve(_ctx: &SolveContext<'_>)"));
+    assert_eq!("", project.read("test_input/day04.txt"));
+}
+
+#[test]
+fn refuses_to_scaffold_over_a_day_directory() {
+    let project = Project::new("directory");
+    project.cache_input("day05", "input");
+    std::fs::create_dir_all(project.root.join("src/days/day05")).unwrap();
+    std::fs::write(project.root.join("src/days/day05/mod.rs"), "// my solution").unwrap();
+
+    for args in [&["5", "--offline"][..], &["5", "--offline", "--overwrite"]] {
+        assert!(!project.aoc(args, None).status.success());
+        assert_eq!("// my solution", project.read("src/days/day05/mod.rs"));
+        assert!(!project.exists("src/days/day05.rs"));
+    }
+}
+
+#[test]
+fn is_idempotent_with_overwrite() {
+    let project = Project::new("idempotent");
+    project.cache_input("day02", "input");
+
+    assert_success(&project.aoc(&["2", "--offline"], None));
+    let mod_file = project.read("src/days/mod.rs");
+    let solver = project.read("src/days/day02.rs");
+
+    assert_success(&project.aoc(&["2", "--offline", "--overwrite"], None));
+    assert_eq!(mod_file, project.read("src/days/mod.rs"));
+    assert_eq!(solver, project.read("src/days/day02.rs"));
+}
+
+#[test]
+fn serves_cached_input_without_downloading() {
+    let project = Project::new("cached");
+    project.cache_input("day02", "cached");
+
+    // the base url points at a closed port, so any download attempt would fail
+    assert_success(&project.aoc(&["2", "-d"], None));
+    assert_eq!(
+        "cached",
+        std::fs::read_to_string(project.cache.join("2025_day02.txt")).unwrap()
+    );
+    assert!(!project.exists("src/days/day02.rs"));
+}
+
+#[test]
+fn offline_fails_when_cache_is_cold() {
+    let project = Project::new("offline");
+
+    let output = project.aoc(&["2", "-d", "--offline"], None);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("offline mode"));
+}
+
+#[test]
+fn fails_on_bad_download() {
+    let project = Project::new("bad-download");
+    let (base_url, server) = test_server::serve_once(500, "oops".to_string());
+
+    let output = project.aoc(&["2", "-d"], Some(&base_url));
+    server.join().unwrap();
+    assert!(!output.status.success());
+    assert!(!project.cache.join("2025_day02.txt").exists());
+}
+
+#[test]
+fn refuses_to_run_outside_a_repository() {
+    let project = Project::new("not-a-repo");
+    std::fs::remove_file(project.root.join("Cargo.toml")).unwrap();
+
+    let output = project.aoc(&["2", "--offline"], None);
+    assert!(!output.status.success());
+    assert!(!project.exists("src/days/day02.rs"));
+    assert_eq!(MOD_FILE, project.read("src/days/mod.rs"));
+}
+
+#[test]
+fn registers_day_around_other_mod_file_content() {
+    let project = Project::new("mod-content");
+    std::fs::write(
+        project.root.join("src/days/mod.rs"),
+        "// registry\ncrate::register_days! {\n    day01,\n\n    // slow\n    day03, // keep me\n}\n\npub mod helpers;\n",
+    )
+    .unwrap();
+    project.cache_input("day02", "");
+
+    assert_success(&project.aoc(&["2", "--offline"], None));
+
+    assert_eq!(
+        "// registry\ncrate::register_days! {\n    day01,\n\n    day02,\n    // slow\n    day03, // keep me\n}\n\npub mod helpers;\n",
+        project.read("src/days/mod.rs")
+    );
+    assert!(!project.exists("src/days/.mod.rs.tmp"));
+}
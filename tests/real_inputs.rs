@@ -0,0 +1,86 @@
+//! Runs every registered day against its real input from `AOC_CACHE`.
+//!
+//! Expected answers come from `answers/dayNN.txt`, with any part missing there falling
+//! back to the answer accepted in the ledger. Days without a cached input or without
+//! any known answers are skipped rather than failed.
+use std::{env::VarError, io::ErrorKind};
+
+use advent_of_code_2025::{
+    days::DAYS, get_answers, get_input, ledger::Ledger, DayResult, SolveContext,
+};
+
+#[test]
+fn works_for_input() {
+    // no AOC_CACHE means no ledger, but one that fails to load is a failure
+    let ledger = match Ledger::for_year(2025) {
+        Ok(ledger) => Some(ledger),
+        Err(err) if is_missing(&err) => None,
+        Err(err) => panic!("failed to load the answer ledger: {err:?}"),
+    };
+    let mut checked = 0;
+    let mut failures = vec![];
+
+    for day in DAYS {
+        let input = match get_input(day.name, false) {
+            Ok(input) => input,
+            Err(err) if is_missing(&err) => {
+                eprintln!(
+                    "skipping {}: real input is not available ({err:#})",
+                    day.name
+                );
+                continue;
+            }
+            Err(err) => panic!("failed to read input for {}: {err:?}", day.name),
+        };
+
+        let answers = get_answers(day.name).unwrap().unwrap_or(DayResult {
+            part1: None,
+            part2: None,
+        });
+        let accepted = |part| ledger.as_ref().and_then(|l| l.correct(day.number, part));
+        let expected = DayResult {
+            part1: answers.part1.or_else(|| accepted(1).cloned()),
+            part2: answers.part2.or_else(|| accepted(2).cloned()),
+        };
+        if expected.part1.is_none() && expected.part2.is_none() {
+            eprintln!(
+                "skipping {}: no answers in answers/{}.txt or the ledger",
+                day.name, day.name
+            );
+            continue;
+        }
+
+        let solution = (day.solve)(&SolveContext::new(&input)).unwrap();
+        checked += 1;
+        // answers files are untyped, so `123` must match a solver returning "123"
+        let parts = [
+            (1, &expected.part1, &solution.part1),
+            (2, &expected.part2, &solution.part2),
+        ];
+        for (part, expected, actual) in parts {
+            let Some(expected) = expected else {
+                continue;
+            };
+            if !actual
+                .as_ref()
+                .is_some_and(|actual| actual.lenient_eq(expected))
+            {
+                failures.push(format!(
+                    "{} part {part}: expected {expected:?}, got {actual:?}",
+                    day.name
+                ));
+            }
+        }
+    }
+
+    eprintln!("checked {checked} of {} days", DAYS.len());
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+fn is_missing(err: &anyhow::Error) -> bool {
+    let root = err.root_cause();
+    root.downcast_ref::<VarError>().is_some()
+        || root
+            .downcast_ref::<std::io::Error>()
+            .is_some_and(|err| err.kind() == ErrorKind::NotFound)
+}
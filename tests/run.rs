@@ -0,0 +1,71 @@
+//! Runs the `run` binary against the example inputs and checks its machine-readable
+//! output and error reporting.
+use std::process::Command;
+
+fn run(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_run"))
+        .args(args)
+        .arg("--test")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "run failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn prints_json_records_per_part() {
+    let output = run(&["4", "--format", "json"]);
+    let records: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let records = records.as_array().unwrap();
+    assert_eq!(2, records.len());
+
+    for (record, (part, answer)) in records.iter().zip([(1, "13"), (2, "43")]) {
+        assert_eq!("day04", record["day"]);
+        assert_eq!(part, record["part"]);
+        assert_eq!(answer, record["answer"]);
+        assert_eq!("Usize", record["answer_type"]);
+        assert!(record["parse_ns"].is_u64());
+        assert!(record["total_ns"].is_u64());
+    }
+}
+
+#[test]
+fn prints_delimited_records() {
+    let csv = run(&["1..=2", "--format", "csv"]);
+    let lines = csv.lines().collect::<Vec<_>>();
+    assert_eq!(
+        "day,part,answer,answer_type,parse_ns,part1_ns,part2_ns,total_ns,total_min_ns,total_max_ns",
+        lines[0]
+    );
+    assert_eq!(5, lines.len());
+    assert!(lines[1].starts_with("day01,1,"));
+    assert!(lines[4].starts_with("day02,2,"));
+
+    let tsv = run(&["1", "--format", "tsv"]);
+    assert!(tsv.lines().nth(1).unwrap().starts_with("day01\t1\t"));
+}
+
+#[test]
+fn points_at_malformed_input() {
+    let cache = std::env::temp_dir().join(format!("aoc-run-{}", std::process::id()));
+    std::fs::create_dir_all(&cache).unwrap();
+    std::fs::write(cache.join("2025_day01.txt"), "L68\nR3x\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_run"))
+        .arg("1")
+        .env("AOC_CACHE", &cache)
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&cache).unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.starts_with("2 | R3x\n  |   ^ expected a digit or the end of the line\n"),
+        "{stderr}"
+    );
+}